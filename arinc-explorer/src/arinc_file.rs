@@ -0,0 +1,204 @@
+//! The behaviour shared by all the ARINC 665 files.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::fs;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use binrw::io::Cursor;
+use binrw::meta::{ReadEndian, WriteEndian};
+use binrw::{BinRead, BinWrite};
+
+use crate::arinc_string::ArincString;
+use crate::convert::ConversionWarning;
+use crate::crc::crc16;
+use crate::error::UserDataError;
+use crate::field_map::Field;
+use crate::format_version::FormatVersion;
+use crate::header::{FileHeader, MediaSetInfo};
+use crate::part_number::PartNumber;
+use crate::user_data::{DecodedUserData, UserDataRegistry};
+use crate::utils::vec16_to_bytes;
+
+/// An ARINC 665 file.
+///
+/// The trait gives access to the fields every file has, and it allows generic
+/// tooling to read, verify and write any ARINC 665 file.
+///
+/// It's implemented by every file type the crate models, `LOADS.LUM` and
+/// `FILES.LUM`. A full model of the load header files (`.LUH`) is out of
+/// scope, their file lists are read by
+/// [`LoadHeaderFiles`](crate::load_header::LoadHeaderFiles), which is not a
+/// file type.
+pub trait ArincFile:
+    Sized + for<'a> BinRead<Args<'a> = ()> + ReadEndian + for<'a> BinWrite<Args<'a> = ()> + WriteEndian
+{
+    #[cfg(feature = "std")]
+    type Error: From<std::io::Error> + From<binrw::Error>;
+    #[cfg(not(feature = "std"))]
+    type Error: From<binrw::Error>;
+
+    fn get_header(&self) -> &FileHeader;
+
+    /// Returns the user defined data words, if the file has any.
+    fn get_user_defined_data_words(&self) -> Option<&[u16]>;
+
+    fn get_file_crc(&self) -> u16;
+
+    /// Replaces the user defined data, [`None`] removes it. Data with an odd
+    /// number of bytes is padded with a zero byte.
+    fn set_user_defined_data(&mut self, user_defined_data: Option<&[u8]>);
+
+    /// Returns the fields of the file in the order they are stored, with
+    /// their location, raw bytes and decoded value. Nested entries are
+    /// flattened, their names are prefixed with the path of the entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the struct can't be written.
+    fn get_field_map(&self) -> Result<Vec<Field>, Self::Error>;
+
+    /// Recomputes the lengths, counts, pointers and the CRC of the file from
+    /// the content of the fields, so that it can be written back.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the content doesn't fit in the file format.
+    fn update_layout(&mut self) -> Result<(), Self::Error>;
+
+    /// Converts the file to another format version and recomputes its
+    /// layout. The fields which don't exist in the target version are
+    /// dropped. `LOADS.LUM` and `FILES.LUM` only convert between the media
+    /// file format versions, see [`FormatVersion::is_media_file`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the file type doesn't support `format_version` or if
+    /// the content doesn't fit in the file format.
+    fn convert(
+        &mut self,
+        format_version: FormatVersion,
+    ) -> Result<Vec<ConversionWarning>, Self::Error>;
+
+    /// Parses a file from its content.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if there is a problem reading the bytes into the
+    /// struct.
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self::read(&mut Cursor::new(bytes))?)
+    }
+
+    /// Parses a file from the filesystem.
+    ///
+    /// The function doesn't have any protection against big file size and
+    /// attemps to read the whole file into the memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the file is missing or if there is a problem reading
+    /// the file into the struct.
+    #[cfg(feature = "std")]
+    fn from_path(path: &Path) -> Result<Self, Self::Error> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    /// Recomputes the layout of the file and writes it to the filesystem.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the content doesn't fit in the file format or if
+    /// the file can't be written.
+    #[cfg(feature = "std")]
+    fn save(&mut self, path: &Path) -> Result<(), Self::Error> {
+        self.update_layout()?;
+        fs::write(path, self.to_bytes()?)?;
+        Ok(())
+    }
+
+    /// Returns the content of the file as it is written to disk.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the struct can't be written.
+    fn to_bytes(&self) -> Result<Vec<u8>, Self::Error> {
+        let mut buf = Vec::new();
+        self.write(&mut Cursor::new(&mut buf))?;
+        Ok(buf)
+    }
+
+    // Returns the number of 16-bit words
+    fn get_file_length(&self) -> u32 {
+        self.get_header().get_file_length()
+    }
+
+    fn get_format_version(&self) -> FormatVersion {
+        self.get_header().get_format_version()
+    }
+
+    fn get_file_type_string(&self) -> String {
+        self.get_header().get_file_type_string()
+    }
+
+    /// Returns the user defined data bytes, if the file has any.
+    fn get_user_defined_data(&self) -> Option<Vec<u8>> {
+        self.get_user_defined_data_words().map(vec16_to_bytes)
+    }
+
+    /// Computes the CRC of the file content, excluding the `file_crc` field.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the struct can't be written.
+    fn compute_file_crc(&self) -> Result<u16, Self::Error> {
+        let buf = self.to_bytes()?;
+        Ok(crc16(&buf[..buf.len().saturating_sub(2)]))
+    }
+
+    /// Returns `true` if the stored CRC matches the file content.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the struct can't be written.
+    fn has_valid_file_crc(&self) -> Result<bool, Self::Error> {
+        Ok(self.compute_file_crc()? == self.get_file_crc())
+    }
+}
+
+/// An ARINC 665 media file, which identifies the media set it belongs to.
+pub trait MediaSetFile: ArincFile {
+    fn get_media_set_info(&self) -> &MediaSetInfo;
+
+    fn get_media_set_pn(&self) -> PartNumber {
+        self.get_media_set_info().get_media_set_pn()
+    }
+
+    /// Returns the media set PN with the raw bytes stored in the file.
+    fn get_media_set_pn_raw(&self) -> ArincString {
+        self.get_media_set_info().get_media_set_pn_raw()
+    }
+
+    /// Returns the sequence number of the media set member (`X` of `X of Y`).
+    fn get_media_sequence_number(&self) -> u8 {
+        self.get_media_set_info().get_media_sequence_number()
+    }
+
+    /// Returns the number of media set members (`Y` of `X of Y`).
+    fn get_number_of_media_set_members(&self) -> u8 {
+        self.get_media_set_info().get_number_of_media_set_members()
+    }
+
+    /// Decodes the user defined data with the decoder `registry` selects for
+    /// the media set PN. Returns [`None`] if the file has no user defined
+    /// data.
+    fn decode_user_defined_data(
+        &self,
+        registry: &UserDataRegistry,
+    ) -> Option<Result<DecodedUserData, UserDataError>> {
+        let data = self.get_user_defined_data()?;
+        Some(registry.select(&self.get_media_set_pn()).decode(&data))
+    }
+}
@@ -0,0 +1,196 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::error::CharsetError;
+use crate::utils::vec16_to_bytes;
+
+/// Character sets used by the ARINC 665 strings.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Charset {
+    /// Upper case letters and digits, used by part numbers.
+    PartNumber,
+    /// Printable ASCII characters, used by filenames, pathnames and target
+    /// hardware IDs.
+    Text,
+}
+impl Charset {
+    #[must_use]
+    pub fn contains(self, byte: u8) -> bool {
+        match self {
+            Charset::PartNumber => byte.is_ascii_uppercase() || byte.is_ascii_digit(),
+            Charset::Text => byte.is_ascii_graphic() || byte == b' ',
+        }
+    }
+}
+
+/// A string as it is stored in an ARINC 665 file.
+///
+/// The strings are stored as a number of characters followed by 16-bit words.
+/// When the number of characters is odd, the last word is padded with a NUL
+/// byte. [`ArincString`] keeps all the bytes, padding included, so that the
+/// exact content of the file is available for diagnostics.
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct ArincString {
+    raw: Vec<u8>,
+    length: usize,
+}
+impl ArincString {
+    /// Constructs a new [`ArincString`] from the words read from the file and
+    /// the declared number of characters.
+    #[must_use]
+    pub fn new(words: &[u16], length: usize) -> Self {
+        let raw = vec16_to_bytes(words);
+        Self { raw, length }
+    }
+
+    /// Returns the declared number of characters.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns all the bytes stored in the file, padding included.
+    #[must_use]
+    pub fn get_raw_bytes(&self) -> &[u8] {
+        &self.raw
+    }
+
+    /// Returns the bytes of the declared characters.
+    #[must_use]
+    pub fn get_content_bytes(&self) -> &[u8] {
+        &self.raw[..self.length.min(self.raw.len())]
+    }
+
+    /// Returns the padding bytes following the declared characters.
+    #[must_use]
+    pub fn get_padding_bytes(&self) -> &[u8] {
+        &self.raw[self.length.min(self.raw.len())..]
+    }
+
+    /// Decodes the characters replacing invalid UTF-8 sequences with
+    /// `U+FFFD`.
+    #[must_use]
+    pub fn to_string_lossy(&self) -> String {
+        String::from_utf8_lossy(self.get_content_bytes()).to_string()
+    }
+
+    /// Returns every character and padding byte which doesn't conform to
+    /// `charset`.
+    #[must_use]
+    pub fn get_issues(&self, charset: Charset) -> Vec<CharsetError> {
+        let mut issues = Vec::new();
+        if self.length > self.raw.len() {
+            issues.push(CharsetError::Truncated {
+                length: self.length,
+                available: self.raw.len(),
+            });
+        }
+        issues.extend(
+            self.get_content_bytes()
+                .iter()
+                .enumerate()
+                .filter(|(_, byte)| !charset.contains(**byte))
+                .map(|(position, byte)| CharsetError::InvalidCharacter {
+                    position,
+                    byte: *byte,
+                }),
+        );
+        issues.extend(
+            self.get_padding_bytes()
+                .iter()
+                .filter(|byte| **byte != 0)
+                .map(|byte| CharsetError::NonNulPadding(*byte)),
+        );
+        issues
+    }
+
+    /// Decodes the characters, rejecting anything which doesn't conform to
+    /// `charset`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first non-conforming character or padding byte.
+    pub fn decode_strict(&self, charset: Charset) -> Result<String, CharsetError> {
+        match self.get_issues(charset).into_iter().next() {
+            Some(issue) => Err(issue),
+            // All the bytes are ASCII at this point.
+            None => Ok(self.to_string_lossy()),
+        }
+    }
+}
+
+/// A non-conforming string found while checking a parsed file.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StringIssue {
+    /// The path of the field, e.g. `loads[0].load_pn`.
+    pub field: String,
+    pub error: CharsetError,
+}
+
+pub(crate) fn push_issues(
+    issues: &mut Vec<StringIssue>,
+    field: &str,
+    string: &ArincString,
+    charset: Charset,
+) {
+    issues.extend(
+        string
+            .get_issues(charset)
+            .into_iter()
+            .map(|error| StringIssue {
+                field: field.to_string(),
+                error,
+            }),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arinc_string() {
+        let string = ArincString::new(&[0x4142, 0x4300], 3);
+        assert_eq!(string.get_raw_bytes(), b"ABC\0");
+        assert_eq!(string.get_content_bytes(), b"ABC");
+        assert_eq!(string.get_padding_bytes(), b"\0");
+        assert_eq!(string.decode_strict(Charset::PartNumber).unwrap(), "ABC");
+
+        let string = ArincString::new(&[0x41e9, 0x4320], 3);
+        assert_eq!(string.to_string_lossy(), "A\u{fffd}C");
+        assert_eq!(
+            string.get_issues(Charset::Text),
+            vec![
+                CharsetError::InvalidCharacter {
+                    position: 1,
+                    byte: 0xe9
+                },
+                CharsetError::NonNulPadding(0x20),
+            ]
+        );
+
+        let string = ArincString::new(&[0x615c], 2);
+        assert!(string.decode_strict(Charset::Text).is_ok());
+        assert_eq!(
+            string.decode_strict(Charset::PartNumber),
+            Err(CharsetError::InvalidCharacter {
+                position: 0,
+                byte: b'a'
+            })
+        );
+
+        let string = ArincString::new(&[0x4142], 3);
+        assert_eq!(
+            string.decode_strict(Charset::Text),
+            Err(CharsetError::Truncated {
+                length: 3,
+                available: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,248 @@
+//! Parsing and verification over `tokio` I/O, enabled by the `async`
+//! feature.
+//!
+//! The verification functions stream the data in chunks, so they don't load
+//! whole files in memory and they don't block the executor. They stop between
+//! two chunks once their [`CancellationToken`] is cancelled; dropping the
+//! future cancels them too.
+
+use std::path::Path;
+
+use tokio::fs;
+use tokio::io::{AsyncRead, AsyncReadExt};
+pub use tokio_util::sync::CancellationToken;
+
+use crate::arinc_file::ArincFile;
+use crate::check_value::{CheckValueHasher, CheckValueType};
+use crate::error::AsyncError;
+use crate::files::File;
+use crate::media_set::MediaSet;
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Parses a file from a reader, which is read to the end.
+///
+/// # Errors
+///
+/// Returns [`Err`] if the reader fails or if there is a problem reading the
+/// bytes into the struct.
+pub async fn read<T: ArincFile, R: AsyncRead + Unpin>(mut reader: R) -> Result<T, T::Error> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes).await?;
+    T::from_bytes(&bytes)
+}
+
+/// Parses a file from the filesystem.
+///
+/// # Errors
+///
+/// Returns [`Err`] if the file is missing or if there is a problem reading
+/// the file into the struct.
+pub async fn read_path<T: ArincFile>(path: &Path) -> Result<T, T::Error> {
+    T::from_bytes(&fs::read(path).await?)
+}
+
+/// Computes the check value of the data of a reader, see
+/// [`CheckValueHasher::finish`].
+///
+/// # Errors
+///
+/// Returns [`Err`] if the check value type is not supported, if the reader
+/// fails or if `cancel` is cancelled.
+pub async fn compute_check_value<R: AsyncRead + Unpin>(
+    mut reader: R,
+    check_value_type: CheckValueType,
+    cancel: &CancellationToken,
+) -> Result<Vec<u8>, AsyncError> {
+    let mut hasher = CheckValueHasher::new(check_value_type)?;
+    let mut buf = vec![0; CHUNK_SIZE];
+    loop {
+        if cancel.is_cancelled() {
+            return Err(AsyncError::Cancelled);
+        }
+        match reader.read(&mut buf).await? {
+            0 => return Ok(hasher.finish()),
+            n => hasher.update(&buf[..n]),
+        }
+    }
+}
+
+/// Returns `true` if the check value of the data of a reader is `expected`.
+///
+/// # Errors
+///
+/// See [`compute_check_value`].
+pub async fn verify_check_value<R: AsyncRead + Unpin>(
+    reader: R,
+    check_value_type: CheckValueType,
+    expected: &[u8],
+    cancel: &CancellationToken,
+) -> Result<bool, AsyncError> {
+    Ok(compute_check_value(reader, check_value_type, cancel).await? == expected)
+}
+
+/// Returns `true` if the CRC-16 of the data of a reader is `expected`, as for
+/// the CRCs listed in `FILES.LUM`.
+///
+/// # Errors
+///
+/// Returns [`Err`] if the reader fails or if `cancel` is cancelled.
+pub async fn verify_crc16<R: AsyncRead + Unpin>(
+    reader: R,
+    expected: u16,
+    cancel: &CancellationToken,
+) -> Result<bool, AsyncError> {
+    verify_check_value(
+        reader,
+        CheckValueType::Crc16,
+        &expected.to_be_bytes(),
+        cancel,
+    )
+    .await
+}
+
+/// Reads a file listed in `FILES.LUM` and returns `true` if its CRC matches.
+///
+/// # Errors
+///
+/// Returns [`Err`] if the file is outside the media root, if it can't be read
+/// or if `cancel` is cancelled.
+pub async fn verify_file_crc(
+    media_set: &MediaSet,
+    file: &File,
+    cancel: &CancellationToken,
+) -> Result<bool, AsyncError> {
+    let reader = fs::File::open(media_set.get_file_path(file)?).await?;
+    verify_crc16(reader, file.get_file_crc(), cancel).await
+}
+
+// Reads a file listed in `FILES.LUM` once and returns `true` if its CRC and
+// its check value, if it has one, match.
+async fn verify_file(
+    media_set: &MediaSet,
+    file: &File,
+    cancel: &CancellationToken,
+) -> Result<bool, AsyncError> {
+    let mut reader = fs::File::open(media_set.get_file_path(file)?).await?;
+    let mut file_crc = CheckValueHasher::new(CheckValueType::Crc16)?;
+    let check_value = file.get_check_value();
+    let mut hasher = check_value
+        .as_ref()
+        .map(|(check_value_type, _)| CheckValueHasher::new(*check_value_type))
+        .transpose()?;
+    let mut buf = vec![0; CHUNK_SIZE];
+    loop {
+        if cancel.is_cancelled() {
+            return Err(AsyncError::Cancelled);
+        }
+        match reader.read(&mut buf).await? {
+            0 => break,
+            n => {
+                file_crc.update(&buf[..n]);
+                if let Some(hasher) = &mut hasher {
+                    hasher.update(&buf[..n]);
+                }
+            }
+        }
+    }
+    Ok(file_crc.finish() == file.get_file_crc().to_be_bytes()
+        && hasher
+            .zip(check_value)
+            .map_or(true, |(hasher, (_, expected))| hasher.finish() == expected))
+}
+
+/// Verifies the CRC of every file listed in `FILES.LUM`, and its check value
+/// if it has one, one after the other. The results are in the order of
+/// `FILES.LUM`.
+///
+/// # Errors
+///
+/// Returns [`Err`] only if `cancel` is cancelled, the errors of the single
+/// files are reported in the results, e.g. a check value of an unsupported
+/// type.
+pub async fn verify_media_set_files(
+    media_set: &MediaSet,
+    cancel: &CancellationToken,
+) -> Result<Vec<Result<bool, AsyncError>>, AsyncError> {
+    let mut results = Vec::new();
+    for file in media_set.get_files_lum().get_media_set_files() {
+        if cancel.is_cancelled() {
+            return Err(AsyncError::Cancelled);
+        }
+        match verify_file(media_set, file, cancel).await {
+            Err(AsyncError::Cancelled) => return Err(AsyncError::Cancelled),
+            x => results.push(x),
+        }
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crc::crc16;
+    use crate::loads::LoadsLum;
+
+    #[tokio::test]
+    async fn test_async_read() {
+        let loads_lum: LoadsLum = read(&include_bytes!("../../test-data/LOADS.LUM")[..])
+            .await
+            .unwrap();
+        assert_eq!(
+            loads_lum,
+            LoadsLum::new_async(Path::new("../test-data/LOADS.LUM"))
+                .await
+                .unwrap()
+        );
+        assert!(read_path::<LoadsLum>(Path::new("missing/LOADS.LUM"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_async_verification() {
+        let data = vec![0x5A; 3 * CHUNK_SIZE + 7];
+        let cancel = CancellationToken::new();
+        assert!(verify_crc16(&data[..], crc16(&data), &cancel)
+            .await
+            .unwrap());
+        assert!(!verify_crc16(&data[..], 0, &cancel).await.unwrap());
+        assert!(matches!(
+            compute_check_value(&data[..], CheckValueType::Md5, &cancel).await,
+            Err(AsyncError::CheckValue(_))
+        ));
+
+        cancel.cancel();
+        assert!(matches!(
+            verify_crc16(&data[..], 0, &cancel).await,
+            Err(AsyncError::Cancelled)
+        ));
+        let media_set = MediaSet::open_async(Path::new("../test-data"))
+            .await
+            .unwrap();
+        assert!(matches!(
+            verify_media_set_files(&media_set, &cancel).await,
+            Err(AsyncError::Cancelled)
+        ));
+
+        // The loads aren't part of the test data.
+        let results = verify_media_set_files(&media_set, &CancellationToken::new())
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 14);
+        assert!(matches!(results[0], Err(AsyncError::FileIO(_))));
+    }
+
+    #[tokio::test]
+    async fn test_async_check_values() {
+        let dir = tempfile::tempdir().unwrap();
+        crate::validate::tests::write_media_with_check_values(dir.path());
+        let media_set = MediaSet::open_async(dir.path()).await.unwrap();
+        let results = verify_media_set_files(&media_set, &CancellationToken::new())
+            .await
+            .unwrap();
+        assert!(matches!(results[0], Ok(true)));
+        assert!(matches!(results[1], Ok(false)));
+        assert!(matches!(results[2], Err(AsyncError::CheckValue(_))));
+    }
+}
@@ -0,0 +1,133 @@
+//! The check values introduced by ARINC 665-3, which protect the files with
+//! a CRC or a digest.
+
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use crate::crc::{crc16_update, crc32_update};
+use crate::error::CheckValueError;
+
+/// The algorithm of a check value, stored in the `check_value_type` fields.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum CheckValueType {
+    /// No check value (0).
+    None,
+    Crc8,
+    Crc16,
+    Crc32,
+    Md5,
+    Sha1,
+    Sha256,
+    Sha512,
+    Unknown(u16),
+}
+impl From<u16> for CheckValueType {
+    fn from(value: u16) -> Self {
+        match value {
+            0 => CheckValueType::None,
+            1 => CheckValueType::Crc8,
+            2 => CheckValueType::Crc16,
+            3 => CheckValueType::Crc32,
+            4 => CheckValueType::Md5,
+            5 => CheckValueType::Sha1,
+            6 => CheckValueType::Sha256,
+            7 => CheckValueType::Sha512,
+            x => CheckValueType::Unknown(x),
+        }
+    }
+}
+impl From<CheckValueType> for u16 {
+    fn from(value: CheckValueType) -> Self {
+        match value {
+            CheckValueType::None => 0,
+            CheckValueType::Crc8 => 1,
+            CheckValueType::Crc16 => 2,
+            CheckValueType::Crc32 => 3,
+            CheckValueType::Md5 => 4,
+            CheckValueType::Sha1 => 5,
+            CheckValueType::Sha256 => 6,
+            CheckValueType::Sha512 => 7,
+            CheckValueType::Unknown(x) => x,
+        }
+    }
+}
+impl Display for CheckValueType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            CheckValueType::None => write!(f, "none"),
+            CheckValueType::Crc8 => write!(f, "CRC-8"),
+            CheckValueType::Crc16 => write!(f, "CRC-16"),
+            CheckValueType::Crc32 => write!(f, "CRC-32"),
+            CheckValueType::Md5 => write!(f, "MD5"),
+            CheckValueType::Sha1 => write!(f, "SHA-1"),
+            CheckValueType::Sha256 => write!(f, "SHA-256"),
+            CheckValueType::Sha512 => write!(f, "SHA-512"),
+            CheckValueType::Unknown(x) => write!(f, "0x{x:04X}"),
+        }
+    }
+}
+
+/// Computes a check value from data supplied in chunks.
+///
+/// Only the CRC-16 and CRC-32 check values are supported.
+#[derive(Debug, Clone)]
+pub struct CheckValueHasher {
+    state: State,
+}
+#[derive(Debug, Clone)]
+enum State {
+    Crc16(u16),
+    Crc32(u32),
+}
+impl CheckValueHasher {
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the check value type is not supported.
+    pub fn new(check_value_type: CheckValueType) -> Result<Self, CheckValueError> {
+        let state = match check_value_type {
+            CheckValueType::Crc16 => State::Crc16(0xFFFF),
+            CheckValueType::Crc32 => State::Crc32(0xFFFF_FFFF),
+            x => return Err(CheckValueError::UnsupportedType(x)),
+        };
+        Ok(Self { state })
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.state = match self.state {
+            State::Crc16(crc) => State::Crc16(crc16_update(crc, data)),
+            State::Crc32(crc) => State::Crc32(crc32_update(crc, data)),
+        };
+    }
+
+    /// Returns the check value as it is stored in the files, most significant
+    /// byte first.
+    #[must_use]
+    pub fn finish(self) -> Vec<u8> {
+        match self.state {
+            State::Crc16(crc) => crc.to_be_bytes().to_vec(),
+            State::Crc32(crc) => (!crc).to_be_bytes().to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_value_hasher() {
+        let mut hasher = CheckValueHasher::new(CheckValueType::Crc32).unwrap();
+        hasher.update(b"1234");
+        hasher.update(b"56789");
+        assert_eq!(hasher.finish(), [0xFC, 0x89, 0x19, 0x18]);
+
+        let mut hasher = CheckValueHasher::new(2.into()).unwrap();
+        hasher.update(b"123456789");
+        assert_eq!(hasher.finish(), [0x29, 0xB1]);
+
+        assert_eq!(
+            CheckValueHasher::new(CheckValueType::Sha256).unwrap_err(),
+            CheckValueError::UnsupportedType(CheckValueType::Sha256)
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use alloc::string::String;
+use core::fmt::{self, Display, Formatter};
+
+use crate::format_version::FormatVersion;
+
+/// A field whose content has been dropped when converting a file to a format
+/// version which doesn't support it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ConversionWarning {
+    /// The path of the field, e.g. `file_check_value`.
+    pub field: String,
+    pub format_version: FormatVersion,
+}
+impl Display for ConversionWarning {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} is not supported by {}, its content has been dropped",
+            self.field, self.format_version
+        )
+    }
+}
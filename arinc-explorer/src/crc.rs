@@ -0,0 +1,69 @@
+/// Computes the ARINC 665 16-bit CRC used by the `file_crc` fields.
+///
+/// This is the CCITT CRC-16 with polynomial 0x1021, initial value 0xFFFF and
+/// no reflection.
+#[must_use]
+pub fn crc16(data: &[u8]) -> u16 {
+    crc16_update(0xFFFF, data)
+}
+
+/// Continues the computation of [`crc16`] with more data, so that large
+/// files can be processed in chunks. The first call takes 0xFFFF as `crc`.
+#[must_use]
+pub fn crc16_update(crc: u16, data: &[u8]) -> u16 {
+    data.iter().fold(crc, |crc, byte| {
+        (0..8).fold(crc ^ (u16::from(*byte) << 8), |crc, _| {
+            if crc & 0x8000 == 0 {
+                crc << 1
+            } else {
+                (crc << 1) ^ 0x1021
+            }
+        })
+    })
+}
+
+/// Computes the ARINC 665 32-bit CRC used by the load CRCs and check values.
+///
+/// The polynomial is 0x04C11DB7, the initial value and the final XOR are
+/// 0xFFFFFFFF and there is no reflection.
+#[must_use]
+pub fn crc32(data: &[u8]) -> u32 {
+    !crc32_update(0xFFFF_FFFF, data)
+}
+
+/// Continues the computation of [`crc32`] with more data. The first call
+/// takes 0xFFFFFFFF as `crc`, the final value is the complement of the
+/// result.
+#[must_use]
+pub fn crc32_update(crc: u32, data: &[u8]) -> u32 {
+    data.iter().fold(crc, |crc, byte| {
+        (0..8).fold(crc ^ (u32::from(*byte) << 24), |crc, _| {
+            if crc & 0x8000_0000 == 0 {
+                crc << 1
+            } else {
+                (crc << 1) ^ 0x04C1_1DB7
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc16() {
+        assert_eq!(crc16(b""), 0xFFFF);
+        assert_eq!(crc16(b"123456789"), 0x29B1);
+        assert_eq!(crc16_update(crc16(b"1234"), b"56789"), 0x29B1);
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"123456789"), 0xFC89_1918);
+        assert_eq!(
+            !crc32_update(crc32_update(0xFFFF_FFFF, b"12"), b"3456789"),
+            0xFC89_1918
+        );
+    }
+}
@@ -0,0 +1,295 @@
+//! The semantic differences between two revisions of the same files.
+//!
+//! Loads are matched by PN and files by pathname, so reordering the entries
+//! doesn't produce differences. Lengths, pointers and the `file_crc` of the
+//! LUM files follow from the content and they aren't compared.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use crate::arinc_file::{ArincFile, MediaSetFile};
+use crate::files::{File, FilesLum};
+use crate::loads::{LoadsLum, Lsp};
+#[cfg(feature = "std")]
+use crate::media_set::MediaSet;
+use crate::utils::vec16_to_hex;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum DifferenceKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A difference between two revisions, see [`diff_loads_lum`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Difference {
+    pub kind: DifferenceKind,
+    /// What changed, e.g. `loads[PN].target_hw_ids` or
+    /// `media_set_files[\DIR\NAME].file_crc`.
+    pub location: String,
+    /// The value in the old revision, [`None`] for additions.
+    pub old: Option<String>,
+    /// The value in the new revision, [`None`] for removals.
+    pub new: Option<String>,
+}
+impl Display for Difference {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let old = self.old.as_deref().unwrap_or_default();
+        let new = self.new.as_deref().unwrap_or_default();
+        match self.kind {
+            DifferenceKind::Added => write!(f, "+ {}: {new}", self.location),
+            DifferenceKind::Removed => write!(f, "- {}: {old}", self.location),
+            DifferenceKind::Changed => write!(f, "~ {}: {old} -> {new}", self.location),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Differences(Vec<Difference>);
+impl Differences {
+    fn added(&mut self, location: impl Into<String>, new: impl Into<String>) {
+        self.0.push(Difference {
+            kind: DifferenceKind::Added,
+            location: location.into(),
+            old: None,
+            new: Some(new.into()),
+        });
+    }
+
+    fn removed(&mut self, location: impl Into<String>, old: impl Into<String>) {
+        self.0.push(Difference {
+            kind: DifferenceKind::Removed,
+            location: location.into(),
+            old: Some(old.into()),
+            new: None,
+        });
+    }
+
+    fn compare<T: PartialEq + ToString>(&mut self, location: impl Into<String>, old: &T, new: &T) {
+        if old != new {
+            self.0.push(Difference {
+                kind: DifferenceKind::Changed,
+                location: location.into(),
+                old: Some(old.to_string()),
+                new: Some(new.to_string()),
+            });
+        }
+    }
+}
+
+fn user_defined_data_string<T: ArincFile>(file: &T) -> String {
+    match file.get_user_defined_data_words() {
+        Some(data) => vec16_to_hex(data),
+        None => "none".to_string(),
+    }
+}
+
+// The fields of the header and of the media set identification.
+fn diff_media_set_file<T: MediaSetFile>(differences: &mut Differences, old: &T, new: &T) {
+    differences.compare(
+        "format_version",
+        &old.get_format_version(),
+        &new.get_format_version(),
+    );
+    differences.compare(
+        "media_set_pn",
+        &old.get_media_set_pn(),
+        &new.get_media_set_pn(),
+    );
+    differences.compare(
+        "media_sequence_number",
+        &old.get_media_sequence_number(),
+        &new.get_media_sequence_number(),
+    );
+    differences.compare(
+        "number_of_media_set_members",
+        &old.get_number_of_media_set_members(),
+        &new.get_number_of_media_set_members(),
+    );
+    differences.compare(
+        "user_defined_data",
+        &user_defined_data_string(old),
+        &user_defined_data_string(new),
+    );
+}
+
+fn diff_load(differences: &mut Differences, location: &str, old: &Lsp, new: &Lsp) {
+    differences.compare(
+        format!("{location}.header_filename"),
+        &old.get_header_filename(),
+        &new.get_header_filename(),
+    );
+    differences.compare(
+        format!("{location}.member_sequence_number"),
+        &old.get_member_sequence_number(),
+        &new.get_member_sequence_number(),
+    );
+    let target_hw_ids = |load: &Lsp| -> BTreeSet<String> {
+        load.get_target_hw_ids()
+            .iter()
+            .map(crate::loads::TargetHW::get_target_hw_id)
+            .collect()
+    };
+    let (old, new) = (target_hw_ids(old), target_hw_ids(new));
+    for x in new.difference(&old) {
+        differences.added(format!("{location}.target_hw_ids"), x.clone());
+    }
+    for x in old.difference(&new) {
+        differences.removed(format!("{location}.target_hw_ids"), x.clone());
+    }
+}
+
+/// Compares two revisions of a `LOADS.LUM`: the header fields, the added and
+/// removed loads and, for the loads with the same PN, their header file,
+/// member and target HW IDs.
+#[must_use]
+pub fn diff_loads_lum(old: &LoadsLum, new: &LoadsLum) -> Vec<Difference> {
+    let mut differences = Differences::default();
+    diff_media_set_file(&mut differences, old, new);
+
+    let loads = |loads_lum: &LoadsLum| -> BTreeMap<String, Lsp> {
+        loads_lum
+            .get_loads()
+            .iter()
+            .map(|x| (x.get_load_pn().to_string(), x.clone()))
+            .collect()
+    };
+    let (old, new) = (loads(old), loads(new));
+    for (load_pn, old_load) in &old {
+        match new.get(load_pn) {
+            Some(new_load) => diff_load(
+                &mut differences,
+                &format!("loads[{load_pn}]"),
+                old_load,
+                new_load,
+            ),
+            None => differences.removed("loads", load_pn.clone()),
+        }
+    }
+    for load_pn in new.keys().filter(|x| !old.contains_key(*x)) {
+        differences.added("loads", load_pn.clone());
+    }
+    differences.0
+}
+
+/// Compares two revisions of a `FILES.LUM`: the header fields, the added and
+/// removed files and, for the files with the same pathname, their CRC and
+/// member.
+#[must_use]
+pub fn diff_files_lum(old: &FilesLum, new: &FilesLum) -> Vec<Difference> {
+    let mut differences = Differences::default();
+    diff_media_set_file(&mut differences, old, new);
+
+    let files = |files_lum: &FilesLum| -> BTreeMap<String, File> {
+        files_lum
+            .get_media_set_files()
+            .iter()
+            .map(|x| {
+                let path = format!("{}{}", x.get_file_pathname(), x.get_file_name());
+                (path, x.clone())
+            })
+            .collect()
+    };
+    let (old, new) = (files(old), files(new));
+    for (path, old_file) in &old {
+        let Some(new_file) = new.get(path) else {
+            differences.removed("media_set_files", path.clone());
+            continue;
+        };
+        differences.compare(
+            format!("media_set_files[{path}].file_crc"),
+            &format!("0x{:04x}", old_file.get_file_crc()),
+            &format!("0x{:04x}", new_file.get_file_crc()),
+        );
+        differences.compare(
+            format!("media_set_files[{path}].member_sequence_number"),
+            &old_file.get_member_sequence_number(),
+            &new_file.get_member_sequence_number(),
+        );
+    }
+    for path in new.keys().filter(|x| !old.contains_key(*x)) {
+        differences.added("media_set_files", path.clone());
+    }
+    differences.0
+}
+
+/// Compares the `LOADS.LUM` and `FILES.LUM` of two media, the locations are
+/// prefixed with the file name, e.g. `LOADS.LUM: loads`.
+#[cfg(feature = "std")]
+#[must_use]
+pub fn diff_media_sets(old: &MediaSet, new: &MediaSet) -> Vec<Difference> {
+    let prefix = |name: &'static str, differences: Vec<Difference>| {
+        differences.into_iter().map(move |x| Difference {
+            location: format!("{name}: {}", x.location),
+            ..x
+        })
+    };
+    prefix(
+        "LOADS.LUM",
+        diff_loads_lum(old.get_loads_lum(), new.get_loads_lum()),
+    )
+    .chain(prefix(
+        "FILES.LUM",
+        diff_files_lum(old.get_files_lum(), new.get_files_lum()),
+    ))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loads::TargetHW;
+    use crate::part_number::PartNumber;
+
+    #[test]
+    fn test_diff_loads_lum() {
+        let old = LoadsLum::from_bytes(include_bytes!("../../test-data/LOADS.LUM")).unwrap();
+        assert!(diff_loads_lum(&old, &old).is_empty());
+
+        let mut new = old.clone();
+        new.get_loads_mut()[0]
+            .get_target_hw_ids_mut()
+            .push(TargetHW::new("QWERTY").unwrap());
+        let mut load = new.get_loads()[0].clone();
+        load.set_load_pn(&PartNumber::new("XYZ0000001")).unwrap();
+        new.get_loads_mut().push(load);
+        let differences = diff_loads_lum(&old, &new);
+        assert_eq!(
+            differences
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            [
+                "+ loads[ABCDEFGH12].target_hw_ids: QWERTY",
+                "+ loads: XYZ0000001",
+            ]
+        );
+        assert_eq!(diff_loads_lum(&new, &old)[1].kind, DifferenceKind::Removed);
+    }
+
+    #[test]
+    fn test_diff_files_lum() {
+        let old = FilesLum::from_bytes(include_bytes!("../../test-data/FILES.LUM")).unwrap();
+        let mut new = old.clone();
+        new.get_media_set_files_mut()[0].set_file_crc(0x1234);
+        new.get_media_set_files_mut().pop();
+        let differences = diff_files_lum(&old, &new);
+        assert_eq!(differences.len(), 2);
+        assert_eq!(
+            differences[0].to_string(),
+            "~ media_set_files[\\ABC1813001\\ABC1813001.LUH].file_crc: 0xd23e -> 0x1234"
+        );
+        assert_eq!(differences[1].kind, DifferenceKind::Removed);
+    }
+}
@@ -0,0 +1,18 @@
+use thiserror::Error;
+
+use super::{CheckValueError, MediaSetError};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    FileIO(#[from] std::io::Error),
+
+    #[error(transparent)]
+    CheckValue(#[from] CheckValueError),
+
+    #[error(transparent)]
+    MediaSet(#[from] MediaSetError),
+
+    #[error("the verification has been cancelled")]
+    Cancelled,
+}
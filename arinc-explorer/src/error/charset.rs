@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum Error {
+    #[error("invalid character 0x{byte:02x} at position {position}")]
+    InvalidCharacter { position: usize, byte: u8 },
+
+    #[error("padding byte 0x{0:02x} is not NUL")]
+    NonNulPadding(u8),
+
+    #[error("{length} characters declared but only {available} bytes stored")]
+    Truncated { length: usize, available: usize },
+}
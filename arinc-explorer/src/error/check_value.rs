@@ -0,0 +1,9 @@
+use thiserror::Error;
+
+use crate::check_value::CheckValueType;
+
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum Error {
+    #[error("{0} check values are not supported")]
+    UnsupportedType(CheckValueType),
+}
@@ -1,10 +1,36 @@
+use alloc::string::String;
+
 use thiserror::Error;
 
+use super::CharsetError;
+use crate::format_version::FormatVersion;
+
 #[derive(Debug, Error)]
 pub enum Error {
+    #[cfg(feature = "std")]
     #[error(transparent)]
     FileIO(#[from] std::io::Error),
 
-    #[error(transparent)]
-    BinRead(#[from] binrw::Error),
+    // binrw errors only implement `Error` with the `std` feature.
+    #[cfg_attr(feature = "std", error(transparent))]
+    #[cfg_attr(not(feature = "std"), error("{0}"))]
+    BinRead(#[cfg_attr(feature = "std", from)] binrw::Error),
+
+    #[error("{field}: {source}")]
+    Charset { field: String, source: CharsetError },
+
+    #[error("{0} doesn't fit in the file format")]
+    Overflow(&'static str),
+
+    #[error("{0} is not a valid hexadecimal string")]
+    InvalidHex(&'static str),
+
+    #[error("{0} is not a supported format version")]
+    UnsupportedFormatVersion(FormatVersion),
+}
+#[cfg(not(feature = "std"))]
+impl From<binrw::Error> for Error {
+    fn from(value: binrw::Error) -> Self {
+        Error::BinRead(value)
+    }
 }
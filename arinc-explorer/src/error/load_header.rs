@@ -0,0 +1,15 @@
+use thiserror::Error;
+
+use crate::format_version::FormatVersion;
+
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum Error {
+    #[error("{0} is not a load header format version")]
+    FormatVersion(FormatVersion),
+
+    #[error("the load header ends before byte {0}")]
+    Truncated(usize),
+
+    #[error("entry {1} of {0} doesn't point to the next entry")]
+    InvalidPointer(&'static str, usize),
+}
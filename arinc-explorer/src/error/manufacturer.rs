@@ -0,0 +1,16 @@
+use alloc::string::String;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[cfg(feature = "std")]
+    #[error(transparent)]
+    FileIO(#[from] std::io::Error),
+
+    #[error("line {line}: expected `MMM,Supplier name`, found {content:?}")]
+    InvalidLine { line: usize, content: String },
+
+    #[error("line {line}: {code:?} is not a three characters manufacturer code")]
+    InvalidCode { line: usize, code: String },
+}
@@ -0,0 +1,18 @@
+use thiserror::Error;
+
+use super::{FilesLumError, LoadsLumError};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    FileIO(#[from] std::io::Error),
+
+    #[error("LOADS.LUM: {0}")]
+    LoadsLum(#[from] LoadsLumError),
+
+    #[error("FILES.LUM: {0}")]
+    FilesLum(#[from] FilesLumError),
+
+    #[error("{0:?} is not a path inside the media set")]
+    UnsafePath(String),
+}
@@ -1,5 +1,27 @@
+#[cfg(feature = "async")]
+mod asynchronous;
+mod charset;
+mod check_value;
 mod files_lum;
+mod load_header;
 mod loads_lum;
+mod manufacturer;
+#[cfg(feature = "std")]
+mod media_set;
+mod part_number;
+mod repair;
+mod user_data;
 
+#[cfg(feature = "async")]
+pub use self::asynchronous::Error as AsyncError;
+pub use self::charset::Error as CharsetError;
+pub use self::check_value::Error as CheckValueError;
 pub use self::files_lum::Error as FilesLumError;
+pub use self::load_header::Error as LoadHeaderError;
 pub use self::loads_lum::Error as LoadsLumError;
+pub use self::manufacturer::Error as ManufacturerError;
+#[cfg(feature = "std")]
+pub use self::media_set::Error as MediaSetError;
+pub use self::part_number::Error as PartNumberError;
+pub use self::repair::Error as RepairError;
+pub use self::user_data::Error as UserDataError;
@@ -0,0 +1,21 @@
+use alloc::string::String;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("invalid character {0:?}")]
+    InvalidCharacter(char),
+
+    #[error("{0} characters are too few for a part number")]
+    TooShort(usize),
+
+    #[error("{0} characters are too many for a part number")]
+    TooLong(usize),
+
+    #[error("check characters {0:?} are not hexadecimal")]
+    InvalidCheckCharacters(String),
+
+    #[error("check characters {found} don't match the expected {expected}")]
+    CheckCharactersMismatch { found: String, expected: String },
+}
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+use super::{FilesLumError, LoadsLumError};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("the file has an odd number of bytes")]
+    OddLength,
+
+    #[error("the file ends before byte {0}")]
+    Truncated(usize),
+
+    #[error("the pointer at byte {0} doesn't lead past its entry within the file")]
+    InvalidPointer(usize),
+
+    #[error(transparent)]
+    LoadsLum(#[from] LoadsLumError),
+
+    #[error(transparent)]
+    FilesLum(#[from] FilesLumError),
+}
@@ -0,0 +1,15 @@
+use alloc::string::String;
+
+use thiserror::Error;
+
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum Error {
+    #[error("{0:?} is not a registered user defined data decoder")]
+    UnknownDecoder(String),
+
+    #[error("byte 0x{byte:02x} at offset {offset} is not printable ASCII")]
+    InvalidAscii { offset: usize, byte: u8 },
+
+    #[error("TLV record at offset {offset} is truncated")]
+    TruncatedRecord { offset: usize },
+}
@@ -0,0 +1,41 @@
+//! The structured representation annotated with the supplier names and the
+//! decoded user defined data, as exported by the `json` and `yaml` formats of
+//! the command line.
+
+use alloc::string::{String, ToString};
+
+use crate::manufacturer::ManufacturerRegistry;
+use crate::part_number::PartNumber;
+use crate::user_data::DecodedUserData;
+
+/// A [`serde::Serialize`] adapter which adds `media_set_supplier`,
+/// `load_supplier` for every load and `user_defined_data_decoded` to the
+/// structured representation of a [`crate::loads::LoadsLum`] or a
+/// [`crate::files::FilesLum`]. The fields without a value are omitted, they
+/// are described by the published JSON Schemas and ignored when
+/// deserializing.
+pub struct Annotated<'a, T> {
+    pub(crate) file: &'a T,
+    registry: &'a ManufacturerRegistry,
+    pub(crate) user_defined_data_decoded: Option<DecodedUserData>,
+}
+impl<'a, T> Annotated<'a, T> {
+    #[must_use]
+    pub fn new(
+        file: &'a T,
+        registry: &'a ManufacturerRegistry,
+        user_defined_data_decoded: Option<DecodedUserData>,
+    ) -> Self {
+        Self {
+            file,
+            registry,
+            user_defined_data_decoded,
+        }
+    }
+
+    pub(crate) fn supplier(&self, part_number: &PartNumber) -> Option<String> {
+        self.registry
+            .get_supplier(part_number)
+            .map(ToString::to_string)
+    }
+}
@@ -0,0 +1,224 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use crate::format_version::FormatVersion;
+
+/// The decoded value of a [`Field`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum FieldValue {
+    Integer(u32),
+    /// Characters of a string, decoded replacing invalid UTF-8 sequences.
+    Text(String),
+    FormatVersion(FormatVersion),
+    /// Data without a known structure, e.g. the user defined data.
+    Bytes(Vec<u8>),
+}
+impl Display for FieldValue {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            FieldValue::Integer(x) => write!(f, "{x}"),
+            FieldValue::Text(x) => write!(f, "{x:?}"),
+            FieldValue::FormatVersion(x) => write!(f, "{x}"),
+            FieldValue::Bytes(x) => write!(f, "{} bytes", x.len()),
+        }
+    }
+}
+
+/// A field of a parsed file, with the location it was read from.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Field {
+    /// The path of the field, e.g. `loads[0].target_hw_ids[1].target_hw_id`.
+    /// Pointers and lengths split in two 16-bit words are reported as a
+    /// single field.
+    pub name: String,
+    /// Offset in bytes from the beginning of the file.
+    pub offset: usize,
+    /// Length in bytes, padding included.
+    pub length: usize,
+    /// Number of padding bytes at the end of the field, e.g. the byte after
+    /// a string with an odd number of characters.
+    pub padding: usize,
+    pub raw: Vec<u8>,
+    pub value: FieldValue,
+}
+impl Field {
+    /// Returns the offset in bytes a pointer field points to. The
+    /// `pointer_to_*` fields count the words from the beginning of the file,
+    /// the pointers to the next entry (`load_pointer`, `file_pointer`) count
+    /// them from the beginning of the entry. Null pointers return [`None`].
+    #[must_use]
+    pub fn get_pointer_target(&self) -> Option<usize> {
+        let name = self.name.rsplit('.').next().unwrap_or_default();
+        let base = if name.starts_with("pointer_to_") {
+            0
+        } else if name.ends_with("_pointer") {
+            self.offset
+        } else {
+            return None;
+        };
+        match self.value {
+            FieldValue::Integer(0) => None,
+            FieldValue::Integer(x) => Some(base + usize::try_from(x).ok()? * 2),
+            _ => None,
+        }
+    }
+}
+
+// Walks the bytes of a file, recording a [`Field`] for each value read. The
+// bytes are always the output of the binrw writer, hence reads past the end
+// only happen for inconsistent structs and they return zeros.
+pub(crate) struct FieldReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    prefix: String,
+    fields: Vec<Field>,
+}
+impl<'a> FieldReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            offset: 0,
+            prefix: String::new(),
+            fields: Vec::new(),
+        }
+    }
+
+    fn push(
+        &mut self,
+        name: &str,
+        length: usize,
+        padding: usize,
+        value: impl FnOnce(&[u8]) -> FieldValue,
+    ) -> &[u8] {
+        let start = self.offset.min(self.bytes.len());
+        let end = (self.offset + length).min(self.bytes.len());
+        let mut raw = self.bytes[start..end].to_vec();
+        raw.resize(length, 0);
+        let value = value(&raw);
+        self.fields.push(Field {
+            name: format!("{}{name}", self.prefix),
+            offset: self.offset,
+            length,
+            padding,
+            raw,
+            value,
+        });
+        self.offset += length;
+        &self.fields[self.fields.len() - 1].raw
+    }
+
+    pub(crate) fn u8(&mut self, name: &str) -> u8 {
+        self.push(name, 1, 0, |raw| FieldValue::Integer(raw[0].into()))[0]
+    }
+
+    pub(crate) fn u16(&mut self, name: &str) -> u16 {
+        let raw = self.push(name, 2, 0, |raw| {
+            FieldValue::Integer(u16::from_be_bytes([raw[0], raw[1]]).into())
+        });
+        u16::from_be_bytes([raw[0], raw[1]])
+    }
+
+    // Reads a value stored as two 16-bit words, most significant first.
+    pub(crate) fn u32(&mut self, name: &str) -> u32 {
+        let raw = self.push(name, 4, 0, |raw| {
+            FieldValue::Integer(u32::from_be_bytes([raw[0], raw[1], raw[2], raw[3]]))
+        });
+        u32::from_be_bytes([raw[0], raw[1], raw[2], raw[3]])
+    }
+
+    pub(crate) fn format_version(&mut self, name: &str) -> FormatVersion {
+        let raw = self.push(name, 2, 0, |raw| {
+            FieldValue::FormatVersion(u16::from_be_bytes([raw[0], raw[1]]).into())
+        });
+        u16::from_be_bytes([raw[0], raw[1]]).into()
+    }
+
+    // Reads the words of a string of `length` characters.
+    pub(crate) fn string(&mut self, name: &str, length: u16) {
+        let length = usize::from(length);
+        let padded = length.div_ceil(2) * 2;
+        self.push(name, padded, padded - length, |raw| {
+            FieldValue::Text(String::from_utf8_lossy(&raw[..length]).to_string())
+        });
+    }
+
+    // Reads a string preceded by its number of characters.
+    pub(crate) fn string_with_length(&mut self, name: &str) {
+        let length = self.u16(&format!("{name}_length"));
+        self.string(name, length);
+    }
+
+    // Reads a block of `count` words, empty blocks are not reported.
+    pub(crate) fn words(&mut self, name: &str, count: usize) {
+        if count == 0 {
+            return;
+        }
+        self.push(name, count * 2, 0, |raw| FieldValue::Bytes(raw.to_vec()));
+    }
+
+    // Reads the fields of a nested entry, prefixing their names with `prefix`.
+    pub(crate) fn nested<T>(&mut self, prefix: &str, f: impl FnOnce(&mut Self) -> T) -> T {
+        let length = self.prefix.len();
+        self.prefix.push_str(prefix);
+        self.prefix.push('.');
+        let result = f(self);
+        self.prefix.truncate(length);
+        result
+    }
+
+    pub(crate) fn into_fields(self) -> Vec<Field> {
+        self.fields
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_reader() {
+        let bytes = [
+            0x00, 0x01, 0x00, 0x02, 0x80, 0x02, 0x00, 0x03, 0x41, 0x42, 0x43, 0x00,
+        ];
+        let mut reader = FieldReader::new(&bytes);
+        assert_eq!(reader.u32("file_length"), 0x10002);
+        assert_eq!(
+            reader.format_version("format_version"),
+            FormatVersion::Arinc665_1
+        );
+        reader.nested("loads[0]", |reader| reader.string_with_length("load_pn"));
+        assert_eq!(reader.u8("past_the_end"), 0);
+
+        let fields = reader.into_fields();
+        assert_eq!(fields.len(), 5);
+        assert_eq!(fields[2].name, "loads[0].load_pn_length");
+        assert_eq!(fields[3].name, "loads[0].load_pn");
+        assert_eq!(fields[3].offset, 8);
+        assert_eq!(fields[3].length, 4);
+        assert_eq!(fields[3].padding, 1);
+        assert_eq!(fields[3].raw, b"ABC\0");
+        assert_eq!(fields[3].value, FieldValue::Text("ABC".to_string()));
+        assert_eq!(fields[4].offset, 12);
+    }
+
+    #[test]
+    fn test_pointer_target() {
+        let mut field = Field {
+            name: "pointer_to_number_of_loads".to_string(),
+            offset: 8,
+            length: 4,
+            padding: 0,
+            raw: vec![0, 0, 0, 0x10],
+            value: FieldValue::Integer(0x10),
+        };
+        assert_eq!(field.get_pointer_target(), Some(0x20));
+        field.name = "loads[0].load_pointer".to_string();
+        assert_eq!(field.get_pointer_target(), Some(0x28));
+        field.value = FieldValue::Integer(0);
+        assert_eq!(field.get_pointer_target(), None);
+        field.name = "loads[0].load_pn_length".to_string();
+        assert_eq!(field.get_pointer_target(), None);
+    }
+}
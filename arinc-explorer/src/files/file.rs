@@ -1,13 +1,49 @@
-use std::fmt::{self, Display, Formatter};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
 
-use binrw::binrw;
+use binrw::{binrw, BinRead, BinResult};
 
-use crate::utils::vec16_to_string;
+use crate::arinc_string::{push_issues, ArincString, Charset, StringIssue};
+use crate::check_value::{CheckValueHasher, CheckValueType};
+use crate::error::{CheckValueError, FilesLumError};
+use crate::field_map::FieldReader;
+#[cfg(feature = "serde")]
+use crate::files::CheckValueRepr;
+use crate::utils::{
+    bytes_to_vec16,
+    expansion_words,
+    read_words_until,
+    string_to_vec16,
+    vec16_to_bytes,
+    vec16_to_string,
+};
+#[cfg(feature = "serde")]
+use crate::utils::{hex_to_vec16, vec16_to_hex};
+
+// Reads the words after the file CRC up to the next entry. The pointer of the
+// last entry is 0, its words end at `end`, which is 0 if it has none.
+#[binrw::parser(reader, endian)]
+fn read_expansion_point_2(file_pointer: u16, size: usize, end: u32) -> BinResult<Vec<u16>> {
+    if file_pointer == 0 {
+        return read_words_until(reader, endian, (end,));
+    }
+    (0..expansion_words(file_pointer, size))
+        .map(|_| u16::read_options(reader, endian, ()))
+        .collect()
+}
 
-// TODO: pass number_of_media_set_files to support ARINC665-5
 #[binrw]
 #[brw(big)]
+#[br(import(end: u32))]
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "FileRepr", try_from = "FileRepr")
+)]
 #[allow(clippy::struct_field_names)]
 pub struct File {
     file_pointer: u16,
@@ -30,20 +66,253 @@ pub struct File {
 
     // #[br(if(file_check_value_length!=0), count = file_check_value_length.div_ceil(2))]
     // file_check_value: Option<Vec<u16>>,
-    // Expansion point no2
+
+    // Expansion point no2, the check value of the file in ARINC 665-3 and
+    // later.
+    #[br(parse_with = read_expansion_point_2, args(file_pointer, 5 + file_name.len() + file_pathname.len(), end))]
+    expansion_point_2: Vec<u16>,
 }
 impl File {
+    /// Constructs a new [`File`] entry.
+    ///
+    /// The file pointer is set when the entry is stored in a
+    /// [`super::FilesLum`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if a string is longer than the format allows.
+    pub fn new(
+        file_name: &str,
+        file_pathname: &str,
+        member_sequence_number: u16,
+        file_crc: u16,
+    ) -> Result<Self, FilesLumError> {
+        Ok(Self {
+            file_pointer: 0,
+            file_name_length: u16::try_from(file_name.len())
+                .map_err(|_| FilesLumError::Overflow("file_name"))?,
+            file_name: string_to_vec16(file_name),
+            file_pathname_length: u16::try_from(file_pathname.len())
+                .map_err(|_| FilesLumError::Overflow("file_pathname"))?,
+            file_pathname: string_to_vec16(file_pathname),
+            member_sequence_number,
+            file_crc,
+            expansion_point_2: Vec::new(),
+        })
+    }
+
+    // Returns the size of the entry in 16-bit words
+    pub(crate) fn get_size(&self) -> usize {
+        5 + self.file_name.len() + self.file_pathname.len() + self.expansion_point_2.len()
+    }
+
+    /// Returns the check value stored after the file CRC by ARINC 665-3 and
+    /// later, most significant byte first, or [`None`] if the entry has no
+    /// check value.
+    #[must_use]
+    pub fn get_check_value(&self) -> Option<(CheckValueType, Vec<u8>)> {
+        let [length, check_value_type, value @ ..] = self.expansion_point_2.as_slice() else {
+            return None;
+        };
+        let bytes = vec16_to_bytes(value);
+        let value = bytes.get(..usize::from(*length))?;
+        (*length != 0).then(|| ((*check_value_type).into(), value.to_vec()))
+    }
+
+    /// Stores a check value after the file CRC, as defined by ARINC 665-3.
+    /// The entries of the other format versions don't have a check value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the check value is longer than the format allows.
+    pub fn set_check_value(
+        &mut self,
+        check_value_type: CheckValueType,
+        check_value: &[u8],
+    ) -> Result<(), FilesLumError> {
+        let length = u16::try_from(check_value.len())
+            .map_err(|_| FilesLumError::Overflow("file_check_value"))?;
+        self.expansion_point_2 = [length, check_value_type.into()]
+            .into_iter()
+            .chain(bytes_to_vec16(check_value))
+            .collect();
+        Ok(())
+    }
+
+    /// Returns `true` if the check value of `content` matches the one of the
+    /// entry, or [`None`] if the entry has no check value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the check value type is not supported, see
+    /// [`CheckValueHasher::new`].
+    pub fn has_valid_check_value(&self, content: &[u8]) -> Result<Option<bool>, CheckValueError> {
+        let Some((check_value_type, check_value)) = self.get_check_value() else {
+            return Ok(None);
+        };
+        let mut hasher = CheckValueHasher::new(check_value_type)?;
+        hasher.update(content);
+        Ok(Some(hasher.finish() == check_value))
+    }
+
+    // Drops the words after the file CRC, which hold the check value of the
+    // file in ARINC 665-3 and later. Returns `true` if there were any.
+    pub(crate) fn drop_check_value(&mut self) -> bool {
+        !core::mem::take(&mut self.expansion_point_2).is_empty()
+    }
+
+    pub(crate) fn read_fields(&self, reader: &mut FieldReader) {
+        reader.u16("file_pointer");
+        reader.string_with_length("file_name");
+        reader.string_with_length("file_pathname");
+        reader.u16("member_sequence_number");
+        reader.u16("file_crc");
+        reader.words("expansion_point_2", self.expansion_point_2.len());
+    }
+
+    // Recomputes the pointer to the next entry, which is 0 for the last
+    // entry.
+    pub(crate) fn update_layout(&mut self, is_last: bool) -> Result<(), FilesLumError> {
+        self.file_pointer = if is_last {
+            0
+        } else {
+            u16::try_from(self.get_size()).map_err(|_| FilesLumError::Overflow("file_pointer"))?
+        };
+        Ok(())
+    }
+
+    #[must_use]
     pub fn get_file_name(&self) -> String {
         // If filename_length is odd, an extra NUL byte is added at the end.
         // This function removes the NUL byte if it exists.
         vec16_to_string(&self.file_name, self.file_name_length as usize)
     }
 
+    #[must_use]
     pub fn get_file_pathname(&self) -> String {
         // If file_pathname_length is odd, an extra NUL byte is added at the end.
         // This function removes the NUL byte if it exists.
         vec16_to_string(&self.file_pathname, self.file_pathname_length as usize)
     }
+
+    #[must_use]
+    pub fn get_member_sequence_number(&self) -> u16 {
+        self.member_sequence_number
+    }
+
+    #[must_use]
+    pub fn get_file_crc(&self) -> u16 {
+        self.file_crc
+    }
+
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the name is longer than the format allows.
+    pub fn set_file_name(&mut self, file_name: &str) -> Result<(), FilesLumError> {
+        self.file_name_length =
+            u16::try_from(file_name.len()).map_err(|_| FilesLumError::Overflow("file_name"))?;
+        self.file_name = string_to_vec16(file_name);
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the pathname is longer than the format allows.
+    pub fn set_file_pathname(&mut self, file_pathname: &str) -> Result<(), FilesLumError> {
+        self.file_pathname_length = u16::try_from(file_pathname.len())
+            .map_err(|_| FilesLumError::Overflow("file_pathname"))?;
+        self.file_pathname = string_to_vec16(file_pathname);
+        Ok(())
+    }
+
+    pub fn set_member_sequence_number(&mut self, member_sequence_number: u16) {
+        self.member_sequence_number = member_sequence_number;
+    }
+
+    pub fn set_file_crc(&mut self, file_crc: u16) {
+        self.file_crc = file_crc;
+    }
+
+    #[must_use]
+    pub fn get_file_name_raw(&self) -> ArincString {
+        ArincString::new(&self.file_name, self.file_name_length as usize)
+    }
+
+    #[must_use]
+    pub fn get_file_pathname_raw(&self) -> ArincString {
+        ArincString::new(&self.file_pathname, self.file_pathname_length as usize)
+    }
+
+    pub(crate) fn push_string_issues(&self, issues: &mut Vec<StringIssue>, prefix: &str) {
+        push_issues(
+            issues,
+            &format!("{prefix}.file_name"),
+            &self.get_file_name_raw(),
+            Charset::Text,
+        );
+        push_issues(
+            issues,
+            &format!("{prefix}.file_pathname"),
+            &self.get_file_pathname_raw(),
+            Charset::Text,
+        );
+    }
+}
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct FileRepr {
+    /// Offset in 16-bit words to the next entry, 0 for the last one.
+    #[serde(default)]
+    file_pointer: u16,
+    file_name: String,
+    file_pathname: String,
+    member_sequence_number: u16,
+    file_crc: u16,
+    /// The check value of the file, ARINC 665-3 and later.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    check_value: Option<CheckValueRepr>,
+}
+#[cfg(feature = "serde")]
+impl From<File> for FileRepr {
+    fn from(value: File) -> Self {
+        Self {
+            file_pointer: value.file_pointer,
+            file_name: value.get_file_name(),
+            file_pathname: value.get_file_pathname(),
+            member_sequence_number: value.member_sequence_number,
+            file_crc: value.file_crc,
+            check_value: value
+                .get_check_value()
+                .map(|(check_value_type, check_value)| CheckValueRepr {
+                    check_value_type: check_value_type.into(),
+                    check_value: vec16_to_hex(&bytes_to_vec16(&check_value)),
+                }),
+        }
+    }
+}
+#[cfg(feature = "serde")]
+impl TryFrom<FileRepr> for File {
+    type Error = FilesLumError;
+
+    fn try_from(value: FileRepr) -> Result<Self, Self::Error> {
+        let mut file = File::new(
+            &value.file_name,
+            &value.file_pathname,
+            value.member_sequence_number,
+            value.file_crc,
+        )?;
+        if let Some(check_value) = value.check_value {
+            let words = hex_to_vec16(&check_value.check_value)
+                .ok_or(FilesLumError::InvalidHex("file_check_value"))?;
+            let bytes = vec16_to_bytes(&words);
+            file.set_check_value(
+                check_value.check_value_type.into(),
+                &bytes[..check_value.check_value.len() / 2],
+            )?;
+        }
+        Ok(file)
+    }
 }
 impl Display for File {
     #[rustfmt::skip]
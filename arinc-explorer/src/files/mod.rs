@@ -1,27 +1,58 @@
-use std::fmt::{self, Display, Formatter};
-use std::fs::File as FsFile; // Rename to avoid conflict with file::File
-use std::io::{Cursor, Read};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
 use std::path::Path;
 
-use binrw::{binrw, BinRead};
-use file::File;
+use binrw::{binrw, BinRead, BinResult};
 
+use crate::arinc_file::{ArincFile, MediaSetFile};
+use crate::arinc_string::{push_issues, Charset, StringIssue};
+use crate::convert::ConversionWarning;
 use crate::error::FilesLumError;
-use crate::file_class::FileClass;
-use crate::utils::{combine_words, vec16_to_string};
+#[cfg(feature = "serde")]
+use crate::export::Annotated;
+use crate::field_map::{Field, FieldReader};
+use crate::format_version::FormatVersion;
+use crate::header::{FileHeader, MediaSetInfo};
+use crate::manufacturer::{supplier_note, DisplayWithManufacturers, ManufacturerRegistry};
+use crate::part_number::{validity_note, PartNumber};
+#[cfg(feature = "serde")]
+use crate::user_data::DecodedUserData;
+use crate::utils::{
+    bytes_to_vec16,
+    combine_words,
+    read_user_defined_data,
+    read_words_until,
+    split_words,
+};
+#[cfg(feature = "serde")]
+use crate::utils::{hex_to_vec16, vec16_to_hex};
 
 mod file;
 
+pub use file::File;
+
+// Reads the file entries, the words of the last one end at `end`.
+#[binrw::parser(reader, endian)]
+fn read_files(count: u16, end: u32) -> BinResult<Vec<File>> {
+    (0..count)
+        .map(|i| File::read_options(reader, endian, (if i + 1 == count { end } else { 0 },)))
+        .collect()
+}
+
 #[binrw]
 #[brw(big)]
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "FilesLumRepr", try_from = "FilesLumRepr")
+)]
 pub struct FilesLum {
-    file_length_msb: u16,
-    file_length_lsb: u16,
-    media_file_format_verion: u16,
-
-    #[br(if(media_file_format_verion!=0x8002))]
-    spare: u16,
+    header: FileHeader,
     pointer_to_media_set_pn_length_msb: u16,
     pointer_to_media_set_pn_length_lsb: u16,
     pointer_to_number_of_media_set_files_msb: u16,
@@ -29,33 +60,37 @@ pub struct FilesLum {
     pointer_to_user_defined_data_msb: u16,
     pointer_to_user_defined_data_lsb: u16,
 
-    #[br(if(media_file_format_verion!=0x8002))]
+    #[br(if(header.media_file_format_verion!=0x8002))]
+    #[bw(if(header.media_file_format_verion!=0x8002))]
     pointer_to_file_check_value_length_msb: u16,
 
-    #[br(if(media_file_format_verion!=0x8002))]
+    #[br(if(header.media_file_format_verion!=0x8002))]
+    #[bw(if(header.media_file_format_verion!=0x8002))]
     pointer_to_file_check_value_length_lsb: u16,
     // Expansion point no1
-    media_set_pn_length: u16, // number of chars
-
-    #[br(count = media_set_pn_length.div_ceil(2))]
-    media_set_pn: Vec<u16>, // It is always an even length
-    media_sequence_number_x: u8,
-    number_of_media_set_members_y: u8,
+    #[br(parse_with = read_words_until, args(combine_words(pointer_to_media_set_pn_length_msb, pointer_to_media_set_pn_length_lsb)))]
+    expansion_point_1: Vec<u16>,
+    media_set: MediaSetInfo,
     number_of_media_set_files: u16,
 
-    #[br(count = number_of_media_set_files)]
+    // The check value of the last file of an ARINC 665-3 file ends where the
+    // next part of the file begins.
+    #[br(parse_with = read_files, args(number_of_media_set_files, if header.get_format_version() == FormatVersion::Arinc665_3Media { [combine_words(pointer_to_user_defined_data_msb, pointer_to_user_defined_data_lsb), combine_words(pointer_to_file_check_value_length_msb, pointer_to_file_check_value_length_lsb), header.get_file_length().saturating_sub(1)].into_iter().find(|x| *x != 0).unwrap_or_default() } else { 0 }))]
     media_set_files: Vec<File>,
     // Expansion point no3
-    #[br(if(combine_words(pointer_to_user_defined_data_msb, pointer_to_user_defined_data_lsb)!=0), count = (combine_words(file_length_msb, file_length_lsb) - combine_words(pointer_to_user_defined_data_msb, pointer_to_user_defined_data_lsb) - 1).div_ceil(2))]
+    #[br(parse_with = read_words_until, args([combine_words(pointer_to_user_defined_data_msb, pointer_to_user_defined_data_lsb), combine_words(pointer_to_file_check_value_length_msb, pointer_to_file_check_value_length_lsb), header.get_file_length().saturating_sub(1)].into_iter().find(|x| *x != 0).unwrap_or_default()))]
+    expansion_point_3: Vec<u16>,
+
+    #[br(parse_with = read_user_defined_data, args(combine_words(pointer_to_user_defined_data_msb, pointer_to_user_defined_data_lsb), match combine_words(pointer_to_file_check_value_length_msb, pointer_to_file_check_value_length_lsb) { 0 => header.get_file_length().saturating_sub(1), x => x }))]
     user_defined_data: Option<Vec<u16>>,
 
-    #[br(if(media_file_format_verion!=0x8002 && combine_words(pointer_to_file_check_value_length_msb, pointer_to_file_check_value_length_lsb)!=0))]
+    #[br(if(header.media_file_format_verion!=0x8002 && combine_words(pointer_to_file_check_value_length_msb, pointer_to_file_check_value_length_lsb)!=0))]
     file_check_value_length: Option<u16>,
 
-    #[br(if(media_file_format_verion!=0x8002 && file_check_value_length.is_some()))]
+    #[br(if(header.media_file_format_verion!=0x8002 && file_check_value_length.is_some()))]
     file_check_value_type: Option<u16>,
 
-    #[br(if(media_file_format_verion!=0x8002 && file_check_value_length.is_some()), count = file_check_value_length.unwrap_or_default().div_ceil(2))]
+    #[br(if(header.media_file_format_verion!=0x8002 && file_check_value_length.is_some()), count = file_check_value_length.unwrap_or_default().div_ceil(2))]
     file_check_value: Option<Vec<u16>>,
 
     file_crc: u16,
@@ -74,25 +109,87 @@ impl FilesLum {
     ///
     /// Returns [`Err`] if the file is missing or if there is a problem reading
     /// the file into the [`FilesLum`] struct.
+    #[cfg(feature = "std")]
     pub fn new(path: &Path) -> Result<Self, FilesLumError> {
-        let mut file = FsFile::open(path)?;
-        let mut buf = Vec::new();
-        file.read_to_end(&mut buf)?;
-        let files_lum = FilesLum::read(&mut Cursor::new(buf))?;
+        Self::from_path(path)
+    }
+
+    /// Constructs a new [`FilesLum`] object, rejecting strings which don't
+    /// conform to the ARINC 665 character sets.
+    ///
+    /// # Arguments
+    ///
+    /// - `path`: the path to the `FILES.LUM` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if [`FilesLum::new`] fails or if a string contains
+    /// non-conforming characters or padding bytes.
+    #[cfg(feature = "std")]
+    pub fn new_strict(path: &Path) -> Result<Self, FilesLumError> {
+        let files_lum = FilesLum::new(path)?;
+        if let Some(issue) = files_lum.get_string_issues().into_iter().next() {
+            return Err(FilesLumError::Charset {
+                field: issue.field,
+                source: issue.error,
+            });
+        }
         Ok(files_lum)
     }
 
-    fn get_file_type_string(&self) -> String {
-        match FileClass::get_file_type(self.media_file_format_verion) {
-            Some(x) => x.to_string(),
-            None => format!("{} unrecognised file class", self.media_file_format_verion),
+    /// Constructs a new [`FilesLum`] object listing `files`, with the lengths,
+    /// pointers and the CRC computed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the format version is not a media file version,
+    /// see [`FormatVersion::is_media_file`], or if the content doesn't fit in
+    /// the file format.
+    pub fn from_files(
+        format_version: FormatVersion,
+        media_set: MediaSetInfo,
+        files: Vec<File>,
+    ) -> Result<Self, FilesLumError> {
+        if !format_version.is_media_file() {
+            return Err(FilesLumError::UnsupportedFormatVersion(format_version));
         }
+        let mut files_lum = Self {
+            header: FileHeader::new(format_version),
+            media_set,
+            media_set_files: files,
+            ..Self::default()
+        };
+        files_lum.update_layout()?;
+        Ok(files_lum)
     }
 
-    // Returns the number of 16-bit words
-    #[must_use]
-    pub fn get_file_length(&self) -> u32 {
-        combine_words(self.file_length_msb, self.file_length_lsb)
+    /// Constructs a new [`FilesLum`] object from the filesystem without
+    /// blocking the executor.
+    ///
+    /// # Arguments
+    ///
+    /// - `path`: the path to the `FILES.LUM` file.
+    ///
+    /// # Errors
+    ///
+    /// See [`FilesLum::new`].
+    #[cfg(feature = "async")]
+    pub async fn new_async(path: &Path) -> Result<Self, FilesLumError> {
+        crate::asynchronous::read_path(path).await
+    }
+
+    /// Constructs a new [`FilesLum`] object from a reader, which is read to the
+    /// end.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the reader fails or if there is a problem reading
+    /// the bytes into the [`FilesLum`] struct.
+    #[cfg(feature = "async")]
+    pub async fn from_async_reader<R: tokio::io::AsyncRead + Unpin>(
+        reader: R,
+    ) -> Result<Self, FilesLumError> {
+        crate::asynchronous::read(reader).await
     }
 
     // Returns the offset in 16-bit words from the beginning of the file
@@ -122,16 +219,356 @@ impl FilesLum {
         )
     }
 
+    // Returns the offset in 16-bit words from the beginning of the file
     #[must_use]
-    pub fn get_media_set_pn(&self) -> String {
-        // If media_set_pn_length is odd, an extra NUL byte is added at the end.
-        // This function removes the NUL byte if it exists.
-        vec16_to_string(&self.media_set_pn, self.media_set_pn_length as usize)
+    pub fn get_pointer_to_file_check_value_length(&self) -> u32 {
+        combine_words(
+            self.pointer_to_file_check_value_length_msb,
+            self.pointer_to_file_check_value_length_lsb,
+        )
+    }
+
+    #[must_use]
+    pub fn get_media_set_files(&self) -> &[File] {
+        &self.media_set_files
+    }
+
+    /// Returns the files for editing. Files can be added, removed and
+    /// reordered; the counts, pointers and the CRC are recomputed by
+    /// [`ArincFile::update_layout`].
+    pub fn get_media_set_files_mut(&mut self) -> &mut Vec<File> {
+        &mut self.media_set_files
+    }
+
+    /// Replaces the media set PN, keeping the media set member numbers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the part number is longer than the format allows.
+    pub fn set_media_set_pn(&mut self, media_set_pn: &PartNumber) -> Result<(), FilesLumError> {
+        self.media_set = MediaSetInfo::new(
+            media_set_pn,
+            self.get_media_sequence_number(),
+            self.get_number_of_media_set_members(),
+        )
+        .ok_or(FilesLumError::Overflow("media_set_pn"))?;
+        Ok(())
+    }
+
+    /// Returns every string which doesn't conform to the ARINC 665 character
+    /// sets, padding bytes included.
+    #[must_use]
+    pub fn get_string_issues(&self) -> Vec<StringIssue> {
+        let mut issues = Vec::new();
+        push_issues(
+            &mut issues,
+            "media_set_pn",
+            &self.get_media_set_pn_raw(),
+            Charset::PartNumber,
+        );
+        for (i, entry) in self.media_set_files.iter().enumerate() {
+            entry.push_string_issues(&mut issues, &format!("media_set_files[{i}]"));
+        }
+        issues
+    }
+}
+impl ArincFile for FilesLum {
+    type Error = FilesLumError;
+
+    fn get_header(&self) -> &FileHeader {
+        &self.header
+    }
+
+    fn get_user_defined_data_words(&self) -> Option<&[u16]> {
+        self.user_defined_data.as_deref()
+    }
+
+    fn get_file_crc(&self) -> u16 {
+        self.file_crc
+    }
+
+    fn set_user_defined_data(&mut self, user_defined_data: Option<&[u8]>) {
+        self.user_defined_data = user_defined_data.map(bytes_to_vec16);
+    }
+
+    fn convert(
+        &mut self,
+        format_version: FormatVersion,
+    ) -> Result<Vec<ConversionWarning>, FilesLumError> {
+        if !format_version.is_media_file() {
+            return Err(FilesLumError::UnsupportedFormatVersion(format_version));
+        }
+        let mut warnings = Vec::new();
+        // The check values of the files were introduced by ARINC 665-3.
+        if self.header.get_format_version() == FormatVersion::Arinc665_3Media
+            && format_version != FormatVersion::Arinc665_3Media
+        {
+            for (i, file) in self.media_set_files.iter_mut().enumerate() {
+                if file.drop_check_value() {
+                    warnings.push(ConversionWarning {
+                        field: format!("media_set_files[{i}].file_check_value"),
+                        format_version,
+                    });
+                }
+            }
+        }
+        self.header
+            .set_format_version(format_version, &mut warnings);
+        if !format_version.has_extended_header() && self.file_check_value.is_some() {
+            warnings.push(ConversionWarning {
+                field: "file_check_value".to_string(),
+                format_version,
+            });
+        }
+        self.update_layout()?;
+        Ok(warnings)
+    }
+
+    fn get_field_map(&self) -> Result<Vec<Field>, FilesLumError> {
+        let bytes = self.to_bytes()?;
+        let mut reader = FieldReader::new(&bytes);
+        let format_version = FileHeader::read_fields(&mut reader);
+        reader.u32("pointer_to_media_set_pn_length");
+        reader.u32("pointer_to_number_of_media_set_files");
+        reader.u32("pointer_to_user_defined_data");
+        if format_version.has_extended_header() {
+            reader.u32("pointer_to_file_check_value_length");
+        }
+        reader.words("expansion_point_1", self.expansion_point_1.len());
+        MediaSetInfo::read_fields(&mut reader);
+        reader.u16("number_of_media_set_files");
+        for (i, file) in self.media_set_files.iter().enumerate() {
+            reader.nested(&format!("media_set_files[{i}]"), |reader| {
+                file.read_fields(reader);
+            });
+        }
+        reader.words("expansion_point_3", self.expansion_point_3.len());
+        if let Some(user_defined_data) = &self.user_defined_data {
+            reader.words("user_defined_data", user_defined_data.len());
+        }
+        if let Some(file_check_value_length) = self.file_check_value_length {
+            reader.u16("file_check_value_length");
+            reader.u16("file_check_value_type");
+            reader.words(
+                "file_check_value",
+                usize::from(file_check_value_length.div_ceil(2)),
+            );
+        }
+        reader.u16("file_crc");
+        Ok(reader.into_fields())
+    }
+
+    fn update_layout(&mut self) -> Result<(), FilesLumError> {
+        let extended_header = self.header.has_extended_header();
+        let header_size = self.header.get_size()
+            + if extended_header { 8 } else { 6 }
+            + self.expansion_point_1.len();
+        let pointer_to_number_of_media_set_files = header_size + self.media_set.get_size();
+        self.number_of_media_set_files = u16::try_from(self.media_set_files.len())
+            .map_err(|_| FilesLumError::Overflow("media_set_files"))?;
+        let number_of_media_set_files = self.media_set_files.len();
+        for (i, file) in self.media_set_files.iter_mut().enumerate() {
+            file.update_layout(i + 1 == number_of_media_set_files)?;
+        }
+        let mut end = pointer_to_number_of_media_set_files
+            + 1
+            + self
+                .media_set_files
+                .iter()
+                .map(File::get_size)
+                .sum::<usize>()
+            + self.expansion_point_3.len();
+        let pointer_to_user_defined_data = match &self.user_defined_data {
+            Some(user_defined_data) => {
+                let pointer = end;
+                end += user_defined_data.len();
+                pointer
+            }
+            None => 0,
+        };
+        if !extended_header {
+            self.file_check_value_length = None;
+            self.file_check_value_type = None;
+            self.file_check_value = None;
+        }
+        let pointer_to_file_check_value_length = match &self.file_check_value {
+            Some(file_check_value) => {
+                let pointer = end;
+                end += 2 + file_check_value.len();
+                pointer
+            }
+            None => 0,
+        };
+        let file_length = end + 1;
+
+        let to_u32 =
+            |value: usize| u32::try_from(value).map_err(|_| FilesLumError::Overflow("file_length"));
+        self.header.set_file_length(to_u32(file_length)?);
+        (
+            self.pointer_to_media_set_pn_length_msb,
+            self.pointer_to_media_set_pn_length_lsb,
+        ) = split_words(to_u32(header_size)?);
+        (
+            self.pointer_to_number_of_media_set_files_msb,
+            self.pointer_to_number_of_media_set_files_lsb,
+        ) = split_words(to_u32(pointer_to_number_of_media_set_files)?);
+        (
+            self.pointer_to_user_defined_data_msb,
+            self.pointer_to_user_defined_data_lsb,
+        ) = split_words(to_u32(pointer_to_user_defined_data)?);
+        (
+            self.pointer_to_file_check_value_length_msb,
+            self.pointer_to_file_check_value_length_lsb,
+        ) = split_words(to_u32(pointer_to_file_check_value_length)?);
+
+        self.file_crc = self.compute_file_crc()?;
+        Ok(())
+    }
+}
+impl MediaSetFile for FilesLum {
+    fn get_media_set_info(&self) -> &MediaSetInfo {
+        &self.media_set
+    }
+}
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub(crate) struct CheckValueRepr {
+    pub(crate) check_value_type: u16,
+    /// Check value as a hexadecimal string.
+    pub(crate) check_value: String,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+/// The structured representation of `FILES.LUM`. The lengths, pointers and
+/// the CRC are recomputed when deserializing.
+struct FilesLumRepr {
+    /// Length of the file in 16-bit words.
+    #[serde(default)]
+    file_length: u32,
+    format_version: FormatVersion,
+    /// Offset in 16-bit words from the beginning of the file.
+    #[serde(default)]
+    pointer_to_media_set_pn_length: u32,
+    /// Offset in 16-bit words from the beginning of the file.
+    #[serde(default)]
+    pointer_to_number_of_media_set_files: u32,
+    /// Offset in 16-bit words from the beginning of the file.
+    #[serde(default)]
+    pointer_to_user_defined_data: u32,
+    /// Offset in 16-bit words from the beginning of the file.
+    #[serde(default)]
+    pointer_to_file_check_value_length: u32,
+    media_set_pn: PartNumber,
+    /// The supplier of the media set PN, only in the annotated export.
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    media_set_supplier: Option<String>,
+    media_sequence_number: u8,
+    number_of_media_set_members: u8,
+    media_set_files: Vec<File>,
+    /// User defined data as a hexadecimal string.
+    user_defined_data: Option<String>,
+    /// The decoded user defined data, only in the annotated export.
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    user_defined_data_decoded: Option<DecodedUserData>,
+    file_check_value: Option<CheckValueRepr>,
+    #[serde(default)]
+    file_crc: u16,
+}
+#[cfg(feature = "serde")]
+impl From<FilesLum> for FilesLumRepr {
+    fn from(value: FilesLum) -> Self {
+        let file_check_value = match (value.file_check_value_type, &value.file_check_value) {
+            (Some(check_value_type), Some(check_value)) => Some(CheckValueRepr {
+                check_value_type,
+                check_value: vec16_to_hex(check_value),
+            }),
+            _ => None,
+        };
+        Self {
+            file_length: value.get_file_length(),
+            format_version: value.get_format_version(),
+            pointer_to_media_set_pn_length: value.get_pointer_to_media_set_pn_length(),
+            pointer_to_number_of_media_set_files: value.get_pointer_to_number_of_media_set_files(),
+            pointer_to_user_defined_data: value.get_pointer_to_user_defined_data(),
+            pointer_to_file_check_value_length: value.get_pointer_to_file_check_value_length(),
+            media_set_pn: value.get_media_set_pn(),
+            media_set_supplier: None,
+            media_sequence_number: value.get_media_sequence_number(),
+            number_of_media_set_members: value.get_number_of_media_set_members(),
+            media_set_files: value.media_set_files,
+            user_defined_data: value.user_defined_data.as_deref().map(vec16_to_hex),
+            user_defined_data_decoded: None,
+            file_check_value,
+            file_crc: value.file_crc,
+        }
+    }
+}
+#[cfg(feature = "serde")]
+impl serde::Serialize for Annotated<'_, FilesLum> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut repr = FilesLumRepr::from(self.file.clone());
+        repr.media_set_supplier = self.supplier(&repr.media_set_pn);
+        repr.user_defined_data_decoded
+            .clone_from(&self.user_defined_data_decoded);
+        serde::Serialize::serialize(&repr, serializer)
+    }
+}
+#[cfg(feature = "serde")]
+impl TryFrom<FilesLumRepr> for FilesLum {
+    type Error = FilesLumError;
+
+    /// Rebuilds the file from its structured representation. The lengths,
+    /// pointers and the CRC are always recomputed.
+    fn try_from(value: FilesLumRepr) -> Result<Self, Self::Error> {
+        let user_defined_data = value
+            .user_defined_data
+            .map(|data| hex_to_vec16(&data).ok_or(FilesLumError::InvalidHex("user_defined_data")))
+            .transpose()?;
+        let (file_check_value_length, file_check_value_type, file_check_value) =
+            match value.file_check_value {
+                Some(check_value) => {
+                    let length = u16::try_from(check_value.check_value.len() / 2)
+                        .map_err(|_| FilesLumError::Overflow("file_check_value"))?;
+                    let words = hex_to_vec16(&check_value.check_value)
+                        .ok_or(FilesLumError::InvalidHex("file_check_value"))?;
+                    (
+                        Some(length),
+                        Some(check_value.check_value_type),
+                        Some(words),
+                    )
+                }
+                None => (None, None, None),
+            };
+        let mut files_lum = FilesLum {
+            header: FileHeader::new(value.format_version),
+            media_set: MediaSetInfo::new(
+                &value.media_set_pn,
+                value.media_sequence_number,
+                value.number_of_media_set_members,
+            )
+            .ok_or(FilesLumError::Overflow("media_set_pn"))?,
+            media_set_files: value.media_set_files,
+            user_defined_data,
+            file_check_value_length,
+            file_check_value_type,
+            file_check_value,
+            ..FilesLum::default()
+        };
+        files_lum.update_layout()?;
+        Ok(files_lum)
     }
 }
 impl Display for FilesLum {
-    #[rustfmt::skip]
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.fmt_with(f, &ManufacturerRegistry::empty())
+    }
+}
+impl DisplayWithManufacturers for FilesLum {
+    #[rustfmt::skip]
+    fn fmt_with(&self, f: &mut Formatter, registry: &ManufacturerRegistry) -> fmt::Result {
         write!(
             f,
             " \
@@ -139,7 +576,7 @@ impl Display for FilesLum {
             {}\n \
             {} | {} | {}  Offsets\n \
             {} Chars in Media Set PN\n \
-            {} Media Set PN\n \
+            {}{}{} Media Set PN\n \
             {} Media number\n \
             {} Total Media sets\n \
             {} Total Media set files\n \
@@ -151,10 +588,12 @@ impl Display for FilesLum {
             self.get_pointer_to_media_set_pn_length(),
             self.get_pointer_to_number_of_media_set_files(),
             self.get_pointer_to_user_defined_data(),
-            self.media_set_pn_length,
+            self.media_set.media_set_pn_length,
             self.get_media_set_pn(),
-            self.media_sequence_number_x,
-            self.number_of_media_set_members_y,
+            supplier_note(registry, &self.get_media_set_pn()),
+            validity_note(&self.get_media_set_pn()),
+            self.get_media_sequence_number(),
+            self.get_number_of_media_set_members(),
             self.number_of_media_set_files,
             self.media_set_files
                 .iter()
@@ -170,6 +609,8 @@ mod tests {
     use std::path::PathBuf;
 
     use super::*;
+    use crate::check_value::CheckValueType;
+    use crate::field_map::FieldValue;
 
     #[test]
     fn test_simple_files_lum() {
@@ -180,18 +621,251 @@ mod tests {
         println!("{files_lum}");
 
         assert_eq!(files_lum.get_file_length(), 300);
-        assert_eq!(files_lum.media_file_format_verion, 0x8002);
+        assert_eq!(files_lum.header.media_file_format_verion, 0x8002);
         assert_eq!(files_lum.get_pointer_to_media_set_pn_length(), 0x9);
         assert_eq!(files_lum.get_pointer_to_number_of_media_set_files(), 0x10);
         assert_eq!(files_lum.get_pointer_to_user_defined_data(), 0x0);
-        assert_eq!(files_lum.media_set_pn_length, 10);
-        assert_eq!(files_lum.media_set_pn.len(), 5);
+        assert_eq!(files_lum.media_set.media_set_pn_length, 10);
+        assert_eq!(files_lum.media_set.media_set_pn.len(), 5);
         assert_eq!(files_lum.get_media_set_pn(), "ABC1813001");
-        assert_eq!(files_lum.media_sequence_number_x, 1);
-        assert_eq!(files_lum.number_of_media_set_members_y, 1);
+        assert_eq!(files_lum.media_set.media_sequence_number_x, 1);
+        assert_eq!(files_lum.media_set.number_of_media_set_members_y, 1);
         assert_eq!(files_lum.number_of_media_set_files, 14);
         assert_eq!(files_lum.media_set_files.len(), 14);
         assert_eq!(files_lum.user_defined_data, None);
         assert_eq!(files_lum.file_crc, 0x3BE8);
     }
+
+    #[test]
+    fn test_files_lum_strings() {
+        let file = PathBuf::from("../test-data/FILES.LUM");
+        let files_lum = FilesLum::new_strict(file.as_path()).unwrap();
+        assert_eq!(files_lum.get_string_issues(), vec![]);
+
+        let file_name = files_lum.media_set_files[1].get_file_name_raw();
+        assert_eq!(file_name.len(), 17);
+        assert_eq!(file_name.get_content_bytes(), b"ABC1813001001.LUP");
+        assert_eq!(file_name.get_padding_bytes(), b"\0");
+    }
+
+    #[test]
+    fn test_files_lum_field_map() {
+        let file = PathBuf::from("../test-data/FILES.LUM");
+        let files_lum = FilesLum::new(file.as_path()).unwrap();
+        let fields = files_lum.get_field_map().unwrap();
+
+        let field = fields
+            .iter()
+            .find(|f| f.name == "media_set_files[1].file_name")
+            .unwrap();
+        assert_eq!(field.offset, 0x10 * 2 + 2 + 18 * 2 + 4);
+        assert_eq!(field.length, 18);
+        assert_eq!(field.raw, b"ABC1813001001.LUP\0");
+        assert_eq!(
+            field.value,
+            FieldValue::Text("ABC1813001001.LUP".to_string())
+        );
+        let last = fields.last().unwrap();
+        assert_eq!(last.offset + last.length, 600);
+    }
+
+    #[test]
+    fn test_files_lum_editing() {
+        let file = PathBuf::from("../test-data/FILES.LUM");
+        let mut files_lum = FilesLum::new(file.as_path()).unwrap();
+        files_lum.expansion_point_3 = vec![0xaaaa];
+        files_lum.get_media_set_files_mut().swap(0, 1);
+        let removed = files_lum.get_media_set_files_mut().remove(2);
+        files_lum.get_media_set_files_mut()[0].set_file_crc(0x1234);
+        files_lum.update_layout().unwrap();
+
+        let edited = FilesLum::from_bytes(&files_lum.to_bytes().unwrap()).unwrap();
+        assert_eq!(edited, files_lum);
+        assert!(edited.has_valid_file_crc().unwrap());
+        assert_eq!(edited.get_media_set_files().len(), 13);
+        assert_eq!(
+            edited.get_media_set_files()[0].get_file_name(),
+            "ABC1813001001.LUP"
+        );
+        assert_eq!(edited.get_media_set_files()[0].get_file_crc(), 0x1234);
+        assert!(!edited.get_media_set_files().contains(&removed));
+        assert_eq!(edited.expansion_point_3, vec![0xaaaa]);
+        assert_eq!(
+            edited.get_file_length() as usize,
+            300 - removed.get_size() + 1
+        );
+    }
+
+    #[test]
+    fn test_files_lum_file_check_values() {
+        let media_set = MediaSetInfo::new(&PartNumber::new("ABC1813001"), 1, 1).unwrap();
+        let mut files = vec![
+            File::new("A.BIN", "\\", 1, 0x1234).unwrap(),
+            File::new("B.BIN", "\\", 1, 0x5678).unwrap(),
+        ];
+        files[0]
+            .set_check_value(CheckValueType::Crc16, &[0xab, 0xcd])
+            .unwrap();
+        files[1]
+            .set_check_value(CheckValueType::Crc32, &[1, 2, 3, 4])
+            .unwrap();
+        let files_lum =
+            FilesLum::from_files(FormatVersion::Arinc665_3Media, media_set.clone(), files).unwrap();
+        // The check value of the last file is read up to the file CRC.
+        let parsed = FilesLum::from_bytes(&files_lum.to_bytes().unwrap()).unwrap();
+        assert_eq!(parsed, files_lum);
+        let check_values = parsed
+            .get_media_set_files()
+            .iter()
+            .map(File::get_check_value)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            check_values,
+            [
+                Some((CheckValueType::Crc16, vec![0xab, 0xcd])),
+                Some((CheckValueType::Crc32, vec![1, 2, 3, 4]))
+            ]
+        );
+
+        for format_version in [
+            FormatVersion::Arinc665_3Load,
+            FormatVersion::Arinc665_3Batch,
+        ] {
+            assert!(matches!(
+                FilesLum::from_files(format_version, media_set.clone(), Vec::new()),
+                Err(FilesLumError::UnsupportedFormatVersion(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_files_lum_convert() {
+        let file = PathBuf::from("../test-data/FILES.LUM");
+        let bytes = std::fs::read(&file).unwrap();
+        let mut files_lum = FilesLum::from_bytes(&bytes).unwrap();
+
+        let warnings = files_lum.convert(FormatVersion::Arinc665_3Media).unwrap();
+        assert_eq!(warnings, vec![]);
+        files_lum.file_check_value_length = Some(2);
+        files_lum.file_check_value_type = Some(1);
+        files_lum.file_check_value = Some(vec![0xabcd]);
+        files_lum.update_layout().unwrap();
+        let mut files_lum = FilesLum::from_bytes(&files_lum.to_bytes().unwrap()).unwrap();
+        assert_eq!(files_lum.get_pointer_to_media_set_pn_length(), 12);
+        assert_eq!(files_lum.get_pointer_to_file_check_value_length(), 302);
+        assert_eq!(files_lum.file_check_value, Some(vec![0xabcd]));
+
+        let warnings = files_lum.convert(FormatVersion::Arinc665_1).unwrap();
+        assert_eq!(
+            warnings,
+            vec![ConversionWarning {
+                field: "file_check_value".to_string(),
+                format_version: FormatVersion::Arinc665_1,
+            }]
+        );
+        // The test file CRC doesn't match its content.
+        let converted = files_lum.to_bytes().unwrap();
+        assert_eq!(converted[..bytes.len() - 2], bytes[..bytes.len() - 2]);
+
+        // A file with a CRC-16 check value after its file CRC.
+        let entry = [
+            0, 10, 0, 1, b'A', 0, 0, 1, b'\\', 0, 0, 1, 0, 0, 0, 2, 0, 1, 0xab, 0xcd,
+        ];
+        let file = File::read_args(&mut std::io::Cursor::new(entry), (0,)).unwrap();
+        files_lum.convert(FormatVersion::Arinc665_3Media).unwrap();
+        files_lum.media_set_files.push(file);
+        files_lum
+            .media_set_files
+            .push(File::new("B", "\\", 1, 0).unwrap());
+        files_lum.update_layout().unwrap();
+        let mut files_lum = FilesLum::from_bytes(&files_lum.to_bytes().unwrap()).unwrap();
+        let warnings = files_lum.convert(FormatVersion::Arinc665_2).unwrap();
+        assert_eq!(
+            warnings
+                .iter()
+                .map(|x| x.field.as_str())
+                .collect::<Vec<_>>(),
+            ["media_set_files[14].file_check_value"]
+        );
+        let converted = files_lum.to_bytes().unwrap();
+        assert_eq!(converted.len(), bytes.len() + 2 * 3 + 2 * 2 * 7);
+
+        assert!(matches!(
+            files_lum.convert(FormatVersion::Unknown(0x1234)),
+            Err(FilesLumError::UnsupportedFormatVersion(_))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_files_lum_serde() {
+        let file = PathBuf::from("../test-data/FILES.LUM");
+        let files_lum = FilesLum::new(file.as_path()).unwrap();
+
+        let json = serde_json::to_value(&files_lum).unwrap();
+        assert_eq!(json["format_version"], "ARINC665-1");
+        assert_eq!(json["media_set_files"][0]["file_pointer"], 18);
+        assert_eq!(
+            json["media_set_files"][0]["file_pathname"],
+            "\\ABC1813001\\"
+        );
+        assert_eq!(json["media_set_files"][0]["file_crc"], 0xd23e);
+        assert_eq!(json["file_check_value"], serde_json::Value::Null);
+
+        let rebuilt: FilesLum = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            rebuilt,
+            FilesLum {
+                file_crc: rebuilt.file_crc,
+                ..files_lum
+            }
+        );
+
+        let mut json = serde_json::to_value(&rebuilt).unwrap();
+        json["format_version"] = "ARINC665-3 media".into();
+        json["user_defined_data"] = "0102".into();
+        json["file_check_value"] =
+            serde_json::json!({ "check_value_type": 2, "check_value": "abcd" });
+        let rebuilt: FilesLum = serde_json::from_value(json).unwrap();
+        assert_eq!(rebuilt.get_pointer_to_media_set_pn_length(), 12);
+        assert_eq!(rebuilt.get_pointer_to_file_check_value_length(), 303);
+        assert_eq!(rebuilt.get_file_length(), 307);
+
+        let buf = rebuilt.to_bytes().unwrap();
+        assert_eq!(FilesLum::from_bytes(&buf).unwrap(), rebuilt);
+        assert!(rebuilt.has_valid_file_crc().unwrap());
+        assert_eq!(rebuilt.get_user_defined_data(), Some(vec![1, 2]));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_files_lum_serde_file_check_values() {
+        let media_set = MediaSetInfo::new(&PartNumber::new("ABC1813001"), 1, 1).unwrap();
+        let mut files = vec![
+            File::new("A.BIN", "\\", 1, 0x1234).unwrap(),
+            File::new("B.BIN", "\\", 1, 0x5678).unwrap(),
+        ];
+        files[0]
+            .set_check_value(CheckValueType::Crc32, &[1, 2, 3, 4])
+            .unwrap();
+        let mut files_lum =
+            FilesLum::from_files(FormatVersion::Arinc665_3Media, media_set, files).unwrap();
+        files_lum.user_defined_data = Some(vec![0x0102]);
+        files_lum.update_layout().unwrap();
+
+        let json = serde_json::to_value(&files_lum).unwrap();
+        assert_eq!(
+            json["media_set_files"][0]["check_value"],
+            serde_json::json!({ "check_value_type": 3, "check_value": "01020304" })
+        );
+        assert!(json["media_set_files"][1].get("check_value").is_none());
+
+        let rebuilt: FilesLum = serde_json::from_value(json).unwrap();
+        assert_eq!(rebuilt, files_lum);
+        assert_eq!(rebuilt.to_bytes().unwrap(), files_lum.to_bytes().unwrap());
+
+        let mut json = serde_json::to_value(&files_lum).unwrap();
+        json["media_set_files"][0]["check_value"]["check_value"] = "0g".into();
+        assert!(serde_json::from_value::<FilesLum>(json).is_err());
+    }
 }
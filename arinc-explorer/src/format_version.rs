@@ -0,0 +1,154 @@
+use alloc::format;
+use alloc::string::String;
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+
+/// The media file format version stored at the beginning of every ARINC 665
+/// file.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum FormatVersion {
+    /// ARINC 665-1 files (0x8002).
+    Arinc665_1,
+    /// ARINC 665-2 files (0x8003).
+    Arinc665_2,
+    /// ARINC 665-3 and later load header files (0x8004).
+    Arinc665_3Load,
+    /// ARINC 665-3 and later batch files (0x9004).
+    Arinc665_3Batch,
+    /// ARINC 665-3 and later media files (0xA004).
+    Arinc665_3Media,
+    Unknown(u16),
+}
+impl FormatVersion {
+    /// Returns `true` if the files of this version have the `spare` word and
+    /// the check value fields introduced after ARINC 665-1.
+    #[must_use]
+    pub fn has_extended_header(self) -> bool {
+        self != FormatVersion::Arinc665_1
+    }
+
+    /// Returns `true` for the versions of the media files, `LOADS.LUM` and
+    /// `FILES.LUM`.
+    #[must_use]
+    pub fn is_media_file(self) -> bool {
+        matches!(
+            self,
+            FormatVersion::Arinc665_1 | FormatVersion::Arinc665_2 | FormatVersion::Arinc665_3Media
+        )
+    }
+}
+impl From<u16> for FormatVersion {
+    fn from(value: u16) -> Self {
+        match value {
+            0x8002 => FormatVersion::Arinc665_1,
+            0x8003 => FormatVersion::Arinc665_2,
+            0x8004 => FormatVersion::Arinc665_3Load,
+            0x9004 => FormatVersion::Arinc665_3Batch,
+            0xA004 => FormatVersion::Arinc665_3Media,
+            x => FormatVersion::Unknown(x),
+        }
+    }
+}
+impl From<FormatVersion> for u16 {
+    fn from(value: FormatVersion) -> Self {
+        match value {
+            FormatVersion::Arinc665_1 => 0x8002,
+            FormatVersion::Arinc665_2 => 0x8003,
+            FormatVersion::Arinc665_3Load => 0x8004,
+            FormatVersion::Arinc665_3Batch => 0x9004,
+            FormatVersion::Arinc665_3Media => 0xA004,
+            FormatVersion::Unknown(x) => x,
+        }
+    }
+}
+impl Display for FormatVersion {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            FormatVersion::Arinc665_1 => write!(f, "ARINC665-1"),
+            FormatVersion::Arinc665_2 => write!(f, "ARINC665-2"),
+            FormatVersion::Arinc665_3Load => write!(f, "ARINC665-3 load"),
+            FormatVersion::Arinc665_3Batch => write!(f, "ARINC665-3 batch"),
+            FormatVersion::Arinc665_3Media => write!(f, "ARINC665-3 media"),
+            FormatVersion::Unknown(x) => write!(f, "0x{x:04X}"),
+        }
+    }
+}
+impl FromStr for FormatVersion {
+    type Err = String;
+
+    /// Parses the names produced by [`Display`] or a `0x` prefixed
+    /// hexadecimal value.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ARINC665-1" => Ok(FormatVersion::Arinc665_1),
+            "ARINC665-2" => Ok(FormatVersion::Arinc665_2),
+            "ARINC665-3 load" => Ok(FormatVersion::Arinc665_3Load),
+            "ARINC665-3 batch" => Ok(FormatVersion::Arinc665_3Batch),
+            "ARINC665-3 media" => Ok(FormatVersion::Arinc665_3Media),
+            _ => s
+                .strip_prefix("0x")
+                .and_then(|x| u16::from_str_radix(x, 16).ok())
+                .map(FormatVersion::from)
+                .ok_or(format!("{s:?} is not a media file format version")),
+        }
+    }
+}
+#[cfg(feature = "serde")]
+impl serde::Serialize for FormatVersion {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FormatVersion {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for FormatVersion {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "FormatVersion".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Media file format version, by name or as a 0x prefixed hexadecimal value.",
+            "anyOf": [
+                {
+                    "type": "string",
+                    "enum": [
+                        "ARINC665-1",
+                        "ARINC665-2",
+                        "ARINC665-3 load",
+                        "ARINC665-3 batch",
+                        "ARINC665-3 media",
+                    ],
+                },
+                {
+                    "type": "string",
+                    "pattern": "^0x[0-9A-F]{4}$",
+                },
+            ],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_version() {
+        for value in [0x8002, 0x8003, 0x8004, 0x9004, 0xA004, 0x1234] {
+            let version = FormatVersion::from(value);
+            assert_eq!(u16::from(version), value);
+            assert_eq!(version.to_string().parse::<FormatVersion>(), Ok(version));
+        }
+        assert_eq!(FormatVersion::from(0x1234).to_string(), "0x1234");
+        assert!(!FormatVersion::Arinc665_1.has_extended_header());
+        assert!(FormatVersion::Arinc665_3Media.has_extended_header());
+        assert!("ARINC665-9".parse::<FormatVersion>().is_err());
+    }
+}
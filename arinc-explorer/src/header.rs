@@ -0,0 +1,173 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use binrw::binrw;
+
+use crate::arinc_string::ArincString;
+use crate::convert::ConversionWarning;
+use crate::field_map::FieldReader;
+use crate::file_class::FileClass;
+use crate::format_version::FormatVersion;
+use crate::part_number::PartNumber;
+use crate::utils::{combine_words, split_words, string_to_vec16, vec16_to_string};
+
+/// The words at the beginning of every ARINC 665 file.
+#[binrw]
+#[brw(big)]
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct FileHeader {
+    pub(crate) file_length_msb: u16,
+    pub(crate) file_length_lsb: u16,
+    pub(crate) media_file_format_verion: u16,
+
+    #[br(if(media_file_format_verion!=0x8002))]
+    #[bw(if(*media_file_format_verion!=0x8002))]
+    pub(crate) spare: u16,
+}
+impl FileHeader {
+    /// Constructs a new header, the file length is set by the file
+    /// `update_layout`.
+    #[must_use]
+    pub fn new(format_version: FormatVersion) -> Self {
+        Self {
+            media_file_format_verion: format_version.into(),
+            ..Self::default()
+        }
+    }
+
+    // Returns the number of 16-bit words
+    #[must_use]
+    pub fn get_file_length(&self) -> u32 {
+        combine_words(self.file_length_msb, self.file_length_lsb)
+    }
+
+    pub(crate) fn set_file_length(&mut self, file_length: u32) {
+        (self.file_length_msb, self.file_length_lsb) = split_words(file_length);
+    }
+
+    #[must_use]
+    pub fn get_format_version(&self) -> FormatVersion {
+        self.media_file_format_verion.into()
+    }
+
+    // Changes the format version, the spare word is dropped when converting
+    // to ARINC 665-1.
+    pub(crate) fn set_format_version(
+        &mut self,
+        format_version: FormatVersion,
+        warnings: &mut Vec<ConversionWarning>,
+    ) {
+        if !format_version.has_extended_header() {
+            if self.has_extended_header() && self.spare != 0 {
+                warnings.push(ConversionWarning {
+                    field: "spare".to_string(),
+                    format_version,
+                });
+            }
+            self.spare = 0;
+        }
+        self.media_file_format_verion = format_version.into();
+    }
+
+    #[must_use]
+    pub fn has_extended_header(&self) -> bool {
+        self.get_format_version().has_extended_header()
+    }
+
+    // Returns the size of the header in 16-bit words
+    pub(crate) fn get_size(&self) -> usize {
+        if self.has_extended_header() {
+            4
+        } else {
+            3
+        }
+    }
+
+    pub(crate) fn read_fields(reader: &mut FieldReader) -> FormatVersion {
+        reader.u32("file_length");
+        let format_version = reader.format_version("format_version");
+        if format_version.has_extended_header() {
+            reader.u16("spare");
+        }
+        format_version
+    }
+
+    #[must_use]
+    pub fn get_file_type_string(&self) -> String {
+        match FileClass::get_file_type(self.media_file_format_verion) {
+            Some(x) => x.to_string(),
+            None => format!("{} unrecognised file class", self.media_file_format_verion),
+        }
+    }
+}
+
+/// The media set identification shared by the media files (`LOADS.LUM`,
+/// `FILES.LUM`).
+#[binrw]
+#[brw(big)]
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct MediaSetInfo {
+    pub(crate) media_set_pn_length: u16, // number of chars
+
+    #[br(count = media_set_pn_length.div_ceil(2))]
+    pub(crate) media_set_pn: Vec<u16>, // It is always an even length
+    pub(crate) media_sequence_number_x: u8,
+    pub(crate) number_of_media_set_members_y: u8,
+}
+impl MediaSetInfo {
+    /// Constructs a new [`MediaSetInfo`], returns [`None`] if the part number
+    /// is longer than the format allows.
+    #[must_use]
+    pub fn new(
+        media_set_pn: &PartNumber,
+        media_sequence_number: u8,
+        number_of_media_set_members: u8,
+    ) -> Option<Self> {
+        Some(Self {
+            media_set_pn_length: u16::try_from(media_set_pn.as_str().len()).ok()?,
+            media_set_pn: string_to_vec16(media_set_pn.as_str()),
+            media_sequence_number_x: media_sequence_number,
+            number_of_media_set_members_y: number_of_media_set_members,
+        })
+    }
+
+    #[must_use]
+    pub fn get_media_set_pn(&self) -> PartNumber {
+        // If media_set_pn_length is odd, an extra NUL byte is added at the end.
+        // This function removes the NUL byte if it exists.
+        PartNumber::new(vec16_to_string(
+            &self.media_set_pn,
+            self.media_set_pn_length as usize,
+        ))
+    }
+
+    /// Returns the media set PN with the raw bytes stored in the file.
+    #[must_use]
+    pub fn get_media_set_pn_raw(&self) -> ArincString {
+        ArincString::new(&self.media_set_pn, self.media_set_pn_length as usize)
+    }
+
+    /// Returns the sequence number of the media set member (`X` of `X of Y`).
+    #[must_use]
+    pub fn get_media_sequence_number(&self) -> u8 {
+        self.media_sequence_number_x
+    }
+
+    /// Returns the number of media set members (`Y` of `X of Y`).
+    #[must_use]
+    pub fn get_number_of_media_set_members(&self) -> u8 {
+        self.number_of_media_set_members_y
+    }
+
+    // Returns the size of the block in 16-bit words
+    pub(crate) fn get_size(&self) -> usize {
+        2 + self.media_set_pn.len()
+    }
+
+    pub(crate) fn read_fields(reader: &mut FieldReader) {
+        reader.string_with_length("media_set_pn");
+        reader.u8("media_sequence_number");
+        reader.u8("number_of_media_set_members");
+    }
+}
@@ -1,4 +1,7 @@
 //! The backend library which processes the ARINC files.
+//!
+//! The crate is `no_std` when the default `std` feature is disabled, the
+//! parsing, CRC and model code only require `alloc`.
 
 #![warn(
     clippy::all,
@@ -7,11 +10,39 @@
     // clippy::missing_docs_in_private_items,
     clippy::unwrap_used
 )]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 // Glossary
 // LSP = Loadable Software Parts
 // MSP = Media Set Parts
-mod error;
+pub mod arinc_file;
+pub mod arinc_string;
+#[cfg(feature = "async")]
+pub mod asynchronous;
+pub mod check_value;
+pub mod convert;
+pub mod crc;
+pub mod diff;
+pub mod error;
+#[cfg(feature = "serde")]
+pub mod export;
+pub mod field_map;
 mod file_class;
 pub mod files;
+pub mod format_version;
+pub mod header;
+pub mod load_header;
 pub mod loads;
+pub mod manufacturer;
+#[cfg(feature = "std")]
+pub mod media_set;
+pub mod part_number;
+pub mod repair;
+#[cfg(feature = "schema")]
+pub mod schema;
+pub mod user_data;
 mod utils;
+#[cfg(feature = "std")]
+pub mod validate;
@@ -0,0 +1,143 @@
+//! A file-list reader for the load header files (`.LUH`).
+//!
+//! [`LoadHeaderFiles`] is not a model of the load header, see
+//! [`crate::arinc_file::ArincFile`]: it only reads the names of the data and
+//! support files by following the pointers of the header. The entry layout
+//! changed between the ARINC 665 versions, but every entry starts with the
+//! pointer to the next one and the file name.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::error::LoadHeaderError;
+use crate::format_version::FormatVersion;
+
+/// The names of the files of a load, as listed in its header file. The rest
+/// of the header is ignored.
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct LoadHeaderFiles {
+    data_files: Vec<String>,
+    support_files: Vec<String>,
+}
+impl LoadHeaderFiles {
+    /// Reads the file lists of a load header.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the file is not a load header or if its pointers
+    /// are outside the file.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, LoadHeaderError> {
+        let reader = WordReader(bytes);
+        // ARINC 665-2 added the part flags word after the format version.
+        let pointers = match FormatVersion::from(reader.u16(2)?) {
+            FormatVersion::Arinc665_1 => 3,
+            FormatVersion::Arinc665_2 | FormatVersion::Arinc665_3Load => 4,
+            x => return Err(LoadHeaderError::FormatVersion(x)),
+        };
+        Ok(Self {
+            data_files: reader.file_names("data files", reader.u32(pointers + 4)?)?,
+            support_files: reader.file_names("support files", reader.u32(pointers + 6)?)?,
+        })
+    }
+
+    #[must_use]
+    pub fn get_data_files(&self) -> &[String] {
+        &self.data_files
+    }
+
+    #[must_use]
+    pub fn get_support_files(&self) -> &[String] {
+        &self.support_files
+    }
+}
+
+// Reads big-endian values at word offsets.
+struct WordReader<'a>(&'a [u8]);
+impl WordReader<'_> {
+    fn bytes(&self, word: usize, length: usize) -> Result<&[u8], LoadHeaderError> {
+        let start = word * 2;
+        self.0
+            .get(start..start + length)
+            .ok_or(LoadHeaderError::Truncated(start + length))
+    }
+
+    fn u16(&self, word: usize) -> Result<u16, LoadHeaderError> {
+        let bytes = self.bytes(word, 2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&self, word: usize) -> Result<usize, LoadHeaderError> {
+        let bytes = self.bytes(word, 4)?;
+        let value = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        usize::try_from(value).map_err(|_| LoadHeaderError::Truncated(usize::MAX))
+    }
+
+    // Reads the names of a list of file entries starting with the number of
+    // entries. A null pointer to the list means there are no entries.
+    fn file_names(
+        &self,
+        list: &'static str,
+        pointer: usize,
+    ) -> Result<Vec<String>, LoadHeaderError> {
+        if pointer == 0 {
+            return Ok(Vec::new());
+        }
+        let count = usize::from(self.u16(pointer)?);
+        let mut entry = pointer + 1;
+        let mut names = Vec::with_capacity(count);
+        for i in 0..count {
+            let length = usize::from(self.u16(entry + 1)?);
+            let name = self.bytes(entry + 2, length)?;
+            names.push(String::from_utf8_lossy(name).to_string());
+            let next = usize::from(self.u16(entry)?);
+            if i + 1 < count {
+                if next == 0 {
+                    return Err(LoadHeaderError::InvalidPointer(list, i));
+                }
+                entry += next;
+            }
+        }
+        Ok(names)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_header_files() {
+        #[rustfmt::skip]
+        let bytes: Vec<u8> = [
+            // file_length, format_version, part_flags
+            0x0000, 0x001c, 0x8003, 0x0000,
+            // pointers to load PN, target HW IDs, data files, support files
+            0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x000e, 0x0000, 0x0016,
+            // pointer to user defined data, unused
+            0x0000, 0x0000,
+            // 2 data files, the first entry points 4 words after
+            0x0002, 0x0004, 0x0003, 0x4142, 0x4300, 0x0000, 0x0002, 0x4445,
+            // 1 support file
+            0x0001, 0x0000, 0x0001, 0x4600,
+        ]
+        .iter()
+        .flat_map(|x: &u16| x.to_be_bytes())
+        .collect();
+        let files = LoadHeaderFiles::from_bytes(&bytes).unwrap();
+        assert_eq!(files.get_data_files(), ["ABC", "DE"]);
+        assert_eq!(files.get_support_files(), ["F"]);
+
+        assert_eq!(
+            LoadHeaderFiles::from_bytes(&bytes[..40]),
+            Err(LoadHeaderError::Truncated(42))
+        );
+        let mut media_file = bytes.clone();
+        media_file[4..6].copy_from_slice(&[0xA0, 0x04]);
+        assert_eq!(
+            LoadHeaderFiles::from_bytes(&media_file),
+            Err(LoadHeaderError::FormatVersion(
+                FormatVersion::Arinc665_3Media
+            ))
+        );
+    }
+}
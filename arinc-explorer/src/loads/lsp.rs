@@ -1,13 +1,27 @@
-use std::fmt::{self, Display, Formatter};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
 
 use binrw::binrw;
 
 use super::target_hw::TargetHW;
-use crate::utils::vec16_to_string;
+use crate::arinc_string::{push_issues, ArincString, Charset, StringIssue};
+use crate::error::LoadsLumError;
+use crate::field_map::FieldReader;
+use crate::manufacturer::{supplier_note, DisplayWithManufacturers, ManufacturerRegistry};
+use crate::part_number::{validity_note, PartNumber};
+use crate::utils::{expansion_words, string_to_vec16, vec16_to_string};
 
 #[binrw]
 #[brw(big)]
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "LspRepr", try_from = "LspRepr")
+)]
 pub struct Lsp {
     load_pointer: u16,
     load_pn_length: u16, // number of chars
@@ -25,30 +39,223 @@ pub struct Lsp {
 
     #[br(count = number_of_target_hw_ids)]
     target_hw_ids: Vec<TargetHW>,
+
     // Expansion point no2
+    #[br(count = expansion_words(load_pointer, 5 + load_pn.len() + header_filename.len() + target_hw_ids.iter().map(TargetHW::get_size).sum::<usize>()))]
+    expansion_point_2: Vec<u16>,
 }
 impl Lsp {
-    pub fn get_load_pn(&self) -> String {
+    /// Constructs a new [`Lsp`] entry.
+    ///
+    /// The load pointer is set when the entry is stored in a
+    /// [`super::LoadsLum`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if a string or the list of target HW IDs is longer
+    /// than the format allows.
+    pub fn new(
+        load_pn: &PartNumber,
+        header_filename: &str,
+        member_sequence_number: u16,
+        target_hw_ids: Vec<TargetHW>,
+    ) -> Result<Self, LoadsLumError> {
+        let mut lsp = Self {
+            load_pointer: 0,
+            load_pn_length: u16::try_from(load_pn.as_str().len())
+                .map_err(|_| LoadsLumError::Overflow("load_pn"))?,
+            load_pn: string_to_vec16(load_pn.as_str()),
+            header_filename_length: u16::try_from(header_filename.len())
+                .map_err(|_| LoadsLumError::Overflow("header_filename"))?,
+            header_filename: string_to_vec16(header_filename),
+            member_sequence_number,
+            number_of_target_hw_ids: 0,
+            target_hw_ids,
+            expansion_point_2: Vec::new(),
+        };
+        lsp.update_layout(true)?;
+        Ok(lsp)
+    }
+
+    // Returns the size of the entry in 16-bit words
+    pub(crate) fn get_size(&self) -> usize {
+        5 + self.load_pn.len()
+            + self.header_filename.len()
+            + self
+                .target_hw_ids
+                .iter()
+                .map(TargetHW::get_size)
+                .sum::<usize>()
+            + self.expansion_point_2.len()
+    }
+
+    // Recomputes the number of target HW IDs and the pointer to the next
+    // entry, which is 0 for the last entry.
+    pub(crate) fn update_layout(&mut self, is_last: bool) -> Result<(), LoadsLumError> {
+        self.number_of_target_hw_ids = u16::try_from(self.target_hw_ids.len())
+            .map_err(|_| LoadsLumError::Overflow("target_hw_ids"))?;
+        self.load_pointer = if is_last {
+            0
+        } else {
+            u16::try_from(self.get_size()).map_err(|_| LoadsLumError::Overflow("load_pointer"))?
+        };
+        Ok(())
+    }
+
+    pub(crate) fn read_fields(&self, reader: &mut FieldReader) {
+        reader.u16("load_pointer");
+        reader.string_with_length("load_pn");
+        reader.string_with_length("header_filename");
+        reader.u16("member_sequence_number");
+        reader.u16("number_of_target_hw_ids");
+        for i in 0..self.target_hw_ids.len() {
+            reader.nested(&format!("target_hw_ids[{i}]"), TargetHW::read_fields);
+        }
+        reader.words("expansion_point_2", self.expansion_point_2.len());
+    }
+
+    #[must_use]
+    pub fn get_load_pn(&self) -> PartNumber {
         // If load_pn_length is odd, an extra NUL byte is added at the end.
         // This function removes the NUL byte if it exists.
-        vec16_to_string(&self.load_pn, self.load_pn_length as usize)
+        PartNumber::new(vec16_to_string(&self.load_pn, self.load_pn_length as usize))
     }
 
+    #[must_use]
     pub fn get_header_filename(&self) -> String {
         // If header_filename_length is odd, an extra NUL byte is added at the end.
         // This function removes the NUL byte if it exists.
         vec16_to_string(&self.header_filename, self.header_filename_length as usize)
     }
+
+    #[must_use]
+    pub fn get_member_sequence_number(&self) -> u16 {
+        self.member_sequence_number
+    }
+
+    #[must_use]
+    pub fn get_target_hw_ids(&self) -> &[TargetHW] {
+        &self.target_hw_ids
+    }
+
+    /// Returns the target HW IDs for editing. IDs can be added, removed and
+    /// reordered; the count is recomputed by the `update_layout` of the
+    /// [`super::LoadsLum`] storing the entry.
+    pub fn get_target_hw_ids_mut(&mut self) -> &mut Vec<TargetHW> {
+        &mut self.target_hw_ids
+    }
+
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the part number is longer than the format allows.
+    pub fn set_load_pn(&mut self, load_pn: &PartNumber) -> Result<(), LoadsLumError> {
+        self.load_pn_length = u16::try_from(load_pn.as_str().len())
+            .map_err(|_| LoadsLumError::Overflow("load_pn"))?;
+        self.load_pn = string_to_vec16(load_pn.as_str());
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the filename is longer than the format allows.
+    pub fn set_header_filename(&mut self, header_filename: &str) -> Result<(), LoadsLumError> {
+        self.header_filename_length = u16::try_from(header_filename.len())
+            .map_err(|_| LoadsLumError::Overflow("header_filename"))?;
+        self.header_filename = string_to_vec16(header_filename);
+        Ok(())
+    }
+
+    pub fn set_member_sequence_number(&mut self, member_sequence_number: u16) {
+        self.member_sequence_number = member_sequence_number;
+    }
+
+    #[must_use]
+    pub fn get_load_pn_raw(&self) -> ArincString {
+        ArincString::new(&self.load_pn, self.load_pn_length as usize)
+    }
+
+    #[must_use]
+    pub fn get_header_filename_raw(&self) -> ArincString {
+        ArincString::new(&self.header_filename, self.header_filename_length as usize)
+    }
+
+    pub(crate) fn push_string_issues(&self, issues: &mut Vec<StringIssue>, prefix: &str) {
+        push_issues(
+            issues,
+            &format!("{prefix}.load_pn"),
+            &self.get_load_pn_raw(),
+            Charset::PartNumber,
+        );
+        push_issues(
+            issues,
+            &format!("{prefix}.header_filename"),
+            &self.get_header_filename_raw(),
+            Charset::Text,
+        );
+        for (i, target_hw) in self.target_hw_ids.iter().enumerate() {
+            target_hw.push_string_issues(issues, &format!("{prefix}.target_hw_ids[{i}]"));
+        }
+    }
+}
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[cfg_attr(
+    feature = "schema",
+    derive(schemars::JsonSchema),
+    schemars(rename = "Lsp")
+)]
+pub(crate) struct LspRepr {
+    /// Offset in 16-bit words to the next entry, 0 for the last one.
+    #[serde(default)]
+    load_pointer: u16,
+    pub(crate) load_pn: PartNumber,
+    /// The supplier of the load PN, only in the annotated export.
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub(crate) load_supplier: Option<String>,
+    header_filename: String,
+    member_sequence_number: u16,
+    target_hw_ids: Vec<TargetHW>,
+}
+#[cfg(feature = "serde")]
+impl From<Lsp> for LspRepr {
+    fn from(value: Lsp) -> Self {
+        Self {
+            load_pointer: value.load_pointer,
+            load_pn: value.get_load_pn(),
+            load_supplier: None,
+            header_filename: value.get_header_filename(),
+            member_sequence_number: value.member_sequence_number,
+            target_hw_ids: value.target_hw_ids,
+        }
+    }
+}
+#[cfg(feature = "serde")]
+impl TryFrom<LspRepr> for Lsp {
+    type Error = LoadsLumError;
+
+    fn try_from(value: LspRepr) -> Result<Self, Self::Error> {
+        Lsp::new(
+            &value.load_pn,
+            &value.header_filename,
+            value.member_sequence_number,
+            value.target_hw_ids,
+        )
+    }
 }
 impl Display for Lsp {
-    #[rustfmt::skip]
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.fmt_with(f, &ManufacturerRegistry::empty())
+    }
+}
+impl DisplayWithManufacturers for Lsp {
+    #[rustfmt::skip]
+    fn fmt_with(&self, f: &mut Formatter, registry: &ManufacturerRegistry) -> fmt::Result {
         write!(
             f,
             " \
             \t{} Offsets\n \
             \t{} Chars in Load PN\n \
-            \t{} Load PN\n \
+            \t{}{}{} Load PN\n \
             \t{} Chars in Header filename\n \
             \t{} Header filename\n \
             \t{} Sequence number\n \
@@ -58,6 +265,8 @@ impl Display for Lsp {
             self.load_pointer,
             self.load_pn_length,
             self.get_load_pn(),
+            supplier_note(registry, &self.get_load_pn()),
+            validity_note(&self.get_load_pn()),
             self.header_filename_length,
             self.get_header_filename(),
             self.member_sequence_number,
@@ -1,28 +1,60 @@
-use std::fmt::{self, Display, Formatter};
-use std::fs::File;
-use std::io::{Cursor, Read};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
 use std::path::Path;
 
-use binrw::{binrw, BinRead};
-use lsp::Lsp;
+use binrw::binrw;
 
+use crate::arinc_file::{ArincFile, MediaSetFile};
+use crate::arinc_string::{push_issues, Charset, StringIssue};
+use crate::convert::ConversionWarning;
 use crate::error::LoadsLumError;
-use crate::file_class::FileClass;
-use crate::utils::{combine_words, vec16_to_string};
+#[cfg(feature = "serde")]
+use crate::export::Annotated;
+use crate::field_map::{Field, FieldReader};
+use crate::format_version::FormatVersion;
+use crate::header::{FileHeader, MediaSetInfo};
+use crate::manufacturer::{
+    supplier_note,
+    DisplayWithManufacturers,
+    ManufacturerRegistry,
+    WithManufacturers,
+};
+use crate::part_number::{validity_note, PartNumber};
+#[cfg(feature = "serde")]
+use crate::user_data::DecodedUserData;
+#[cfg(feature = "serde")]
+use crate::utils::hex_to_vec16;
+use crate::utils::{
+    bytes_to_vec16,
+    combine_words,
+    read_user_defined_data,
+    read_words_until,
+    split_words,
+    vec16_to_hex,
+};
 
 mod lsp;
 mod target_hw;
 
+pub use lsp::Lsp;
+#[cfg(feature = "serde")]
+use lsp::LspRepr;
+pub use target_hw::TargetHW;
+
 #[binrw]
 #[brw(big)]
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "LoadsLumRepr", try_from = "LoadsLumRepr")
+)]
 pub struct LoadsLum {
-    file_length_msb: u16,
-    file_length_lsb: u16,
-    media_file_format_verion: u16,
-
-    #[br(if(media_file_format_verion!=0x8002))]
-    spare: u16,
+    header: FileHeader,
     pointer_to_media_set_pn_length_msb: u16,
     pointer_to_media_set_pn_length_lsb: u16,
     pointer_to_number_of_loads_msb: u16,
@@ -30,18 +62,18 @@ pub struct LoadsLum {
     pointer_to_user_defined_data_msb: u16,
     pointer_to_user_defined_data_lsb: u16,
     // Expansion point no1
-    media_set_pn_length: u16, // number of chars
-
-    #[br(count = media_set_pn_length.div_ceil(2))]
-    media_set_pn: Vec<u16>, // It is always an even length
-    media_sequence_number_x: u8,
-    number_of_media_set_members_y: u8,
+    #[br(parse_with = read_words_until, args(combine_words(pointer_to_media_set_pn_length_msb, pointer_to_media_set_pn_length_lsb)))]
+    expansion_point_1: Vec<u16>,
+    media_set: MediaSetInfo,
     number_of_loads: u16,
 
     #[br(count = number_of_loads)]
     loads: Vec<Lsp>,
     // Expansion point no3
-    #[br(if(combine_words(pointer_to_user_defined_data_msb, pointer_to_user_defined_data_lsb)!=0), count = (combine_words(file_length_msb, file_length_lsb) - combine_words(pointer_to_user_defined_data_msb, pointer_to_user_defined_data_lsb) - 1).div_ceil(2))]
+    #[br(parse_with = read_words_until, args(match combine_words(pointer_to_user_defined_data_msb, pointer_to_user_defined_data_lsb) { 0 => header.get_file_length().saturating_sub(1), x => x }))]
+    expansion_point_3: Vec<u16>,
+
+    #[br(parse_with = read_user_defined_data, args(combine_words(pointer_to_user_defined_data_msb, pointer_to_user_defined_data_lsb), header.get_file_length().saturating_sub(1)))]
     user_defined_data: Option<Vec<u16>>,
 
     file_crc: u16,
@@ -60,25 +92,87 @@ impl LoadsLum {
     ///
     /// Returns [`Err`] if the file is missing or if there is a problem reading
     /// the file into the [`LoadsLum`] struct.
+    #[cfg(feature = "std")]
     pub fn new(path: &Path) -> Result<Self, LoadsLumError> {
-        let mut file = File::open(path)?;
-        let mut buf = Vec::new();
-        file.read_to_end(&mut buf)?;
-        let loads_lum = LoadsLum::read(&mut Cursor::new(buf))?;
+        Self::from_path(path)
+    }
+
+    /// Constructs a new [`LoadsLum`] object, rejecting strings which don't
+    /// conform to the ARINC 665 character sets.
+    ///
+    /// # Arguments
+    ///
+    /// - `path`: the path to the `LOADS.LUM` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if [`LoadsLum::new`] fails or if a string contains
+    /// non-conforming characters or padding bytes.
+    #[cfg(feature = "std")]
+    pub fn new_strict(path: &Path) -> Result<Self, LoadsLumError> {
+        let loads_lum = LoadsLum::new(path)?;
+        if let Some(issue) = loads_lum.get_string_issues().into_iter().next() {
+            return Err(LoadsLumError::Charset {
+                field: issue.field,
+                source: issue.error,
+            });
+        }
         Ok(loads_lum)
     }
 
-    fn get_file_type_string(&self) -> String {
-        match FileClass::get_file_type(self.media_file_format_verion) {
-            Some(x) => x.to_string(),
-            None => format!("{} unrecognised file class", self.media_file_format_verion),
+    /// Constructs a new [`LoadsLum`] object listing `loads`, with the lengths,
+    /// pointers and the CRC computed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the format version is not a media file version,
+    /// see [`FormatVersion::is_media_file`], or if the content doesn't fit in
+    /// the file format.
+    pub fn from_loads(
+        format_version: FormatVersion,
+        media_set: MediaSetInfo,
+        loads: Vec<Lsp>,
+    ) -> Result<Self, LoadsLumError> {
+        if !format_version.is_media_file() {
+            return Err(LoadsLumError::UnsupportedFormatVersion(format_version));
         }
+        let mut loads_lum = Self {
+            header: FileHeader::new(format_version),
+            media_set,
+            loads,
+            ..Self::default()
+        };
+        loads_lum.update_layout()?;
+        Ok(loads_lum)
     }
 
-    // Returns the number of 16-bit words
-    #[must_use]
-    pub fn get_file_length(&self) -> u32 {
-        combine_words(self.file_length_msb, self.file_length_lsb)
+    /// Constructs a new [`LoadsLum`] object from the filesystem without
+    /// blocking the executor.
+    ///
+    /// # Arguments
+    ///
+    /// - `path`: the path to the `LOADS.LUM` file.
+    ///
+    /// # Errors
+    ///
+    /// See [`LoadsLum::new`].
+    #[cfg(feature = "async")]
+    pub async fn new_async(path: &Path) -> Result<Self, LoadsLumError> {
+        crate::asynchronous::read_path(path).await
+    }
+
+    /// Constructs a new [`LoadsLum`] object from a reader, which is read to the
+    /// end.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the reader fails or if there is a problem reading
+    /// the bytes into the [`LoadsLum`] struct.
+    #[cfg(feature = "async")]
+    pub async fn from_async_reader<R: tokio::io::AsyncRead + Unpin>(
+        reader: R,
+    ) -> Result<Self, LoadsLumError> {
+        crate::asynchronous::read(reader).await
     }
 
     // Returns the offset in 16-bit words from the beginning of the file
@@ -109,15 +203,257 @@ impl LoadsLum {
     }
 
     #[must_use]
-    pub fn get_media_set_pn(&self) -> String {
-        // If media_set_pn_length is odd, an extra NUL byte is added at the end.
-        // This function removes the NUL byte if it exists.
-        vec16_to_string(&self.media_set_pn, self.media_set_pn_length as usize)
+    pub fn get_loads(&self) -> &[Lsp] {
+        &self.loads
+    }
+
+    /// Returns the loads for editing. Loads can be added, removed and
+    /// reordered; the counts, pointers and the CRC are recomputed by
+    /// [`ArincFile::update_layout`].
+    pub fn get_loads_mut(&mut self) -> &mut Vec<Lsp> {
+        &mut self.loads
+    }
+
+    /// Replaces the media set PN, keeping the media set member numbers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the part number is longer than the format allows.
+    pub fn set_media_set_pn(&mut self, media_set_pn: &PartNumber) -> Result<(), LoadsLumError> {
+        self.media_set = MediaSetInfo::new(
+            media_set_pn,
+            self.get_media_sequence_number(),
+            self.get_number_of_media_set_members(),
+        )
+        .ok_or(LoadsLumError::Overflow("media_set_pn"))?;
+        Ok(())
+    }
+
+    /// Returns every string which doesn't conform to the ARINC 665 character
+    /// sets, padding bytes included.
+    #[must_use]
+    pub fn get_string_issues(&self) -> Vec<StringIssue> {
+        let mut issues = Vec::new();
+        push_issues(
+            &mut issues,
+            "media_set_pn",
+            &self.get_media_set_pn_raw(),
+            Charset::PartNumber,
+        );
+        for (i, entry) in self.loads.iter().enumerate() {
+            entry.push_string_issues(&mut issues, &format!("loads[{i}]"));
+        }
+        issues
+    }
+}
+impl ArincFile for LoadsLum {
+    type Error = LoadsLumError;
+
+    fn get_header(&self) -> &FileHeader {
+        &self.header
+    }
+
+    fn get_user_defined_data_words(&self) -> Option<&[u16]> {
+        self.user_defined_data.as_deref()
+    }
+
+    fn get_file_crc(&self) -> u16 {
+        self.file_crc
+    }
+
+    fn set_user_defined_data(&mut self, user_defined_data: Option<&[u8]>) {
+        self.user_defined_data = user_defined_data.map(bytes_to_vec16);
+    }
+
+    fn convert(
+        &mut self,
+        format_version: FormatVersion,
+    ) -> Result<Vec<ConversionWarning>, LoadsLumError> {
+        if !format_version.is_media_file() {
+            return Err(LoadsLumError::UnsupportedFormatVersion(format_version));
+        }
+        let mut warnings = Vec::new();
+        self.header
+            .set_format_version(format_version, &mut warnings);
+        self.update_layout()?;
+        Ok(warnings)
+    }
+
+    fn get_field_map(&self) -> Result<Vec<Field>, LoadsLumError> {
+        let bytes = self.to_bytes()?;
+        let mut reader = FieldReader::new(&bytes);
+        FileHeader::read_fields(&mut reader);
+        reader.u32("pointer_to_media_set_pn_length");
+        reader.u32("pointer_to_number_of_loads");
+        reader.u32("pointer_to_user_defined_data");
+        reader.words("expansion_point_1", self.expansion_point_1.len());
+        MediaSetInfo::read_fields(&mut reader);
+        reader.u16("number_of_loads");
+        for (i, load) in self.loads.iter().enumerate() {
+            reader.nested(&format!("loads[{i}]"), |reader| load.read_fields(reader));
+        }
+        reader.words("expansion_point_3", self.expansion_point_3.len());
+        if let Some(user_defined_data) = &self.user_defined_data {
+            reader.words("user_defined_data", user_defined_data.len());
+        }
+        reader.u16("file_crc");
+        Ok(reader.into_fields())
+    }
+
+    fn update_layout(&mut self) -> Result<(), LoadsLumError> {
+        let header_size = self.header.get_size() + 6 + self.expansion_point_1.len();
+        let pointer_to_number_of_loads = header_size + self.media_set.get_size();
+        self.number_of_loads =
+            u16::try_from(self.loads.len()).map_err(|_| LoadsLumError::Overflow("loads"))?;
+        let number_of_loads = self.loads.len();
+        for (i, load) in self.loads.iter_mut().enumerate() {
+            load.update_layout(i + 1 == number_of_loads)?;
+        }
+        let mut end = pointer_to_number_of_loads
+            + 1
+            + self.loads.iter().map(Lsp::get_size).sum::<usize>()
+            + self.expansion_point_3.len();
+        let pointer_to_user_defined_data = match &self.user_defined_data {
+            Some(user_defined_data) => {
+                let pointer = end;
+                end += user_defined_data.len();
+                pointer
+            }
+            None => 0,
+        };
+        let file_length = end + 1;
+
+        let to_u32 =
+            |value: usize| u32::try_from(value).map_err(|_| LoadsLumError::Overflow("file_length"));
+        self.header.set_file_length(to_u32(file_length)?);
+        (
+            self.pointer_to_media_set_pn_length_msb,
+            self.pointer_to_media_set_pn_length_lsb,
+        ) = split_words(to_u32(header_size)?);
+        (
+            self.pointer_to_number_of_loads_msb,
+            self.pointer_to_number_of_loads_lsb,
+        ) = split_words(to_u32(pointer_to_number_of_loads)?);
+        (
+            self.pointer_to_user_defined_data_msb,
+            self.pointer_to_user_defined_data_lsb,
+        ) = split_words(to_u32(pointer_to_user_defined_data)?);
+
+        self.file_crc = self.compute_file_crc()?;
+        Ok(())
+    }
+}
+impl MediaSetFile for LoadsLum {
+    fn get_media_set_info(&self) -> &MediaSetInfo {
+        &self.media_set
+    }
+}
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+/// The structured representation of `LOADS.LUM`. The lengths, pointers and
+/// the CRC are recomputed when deserializing.
+struct LoadsLumRepr {
+    /// Length of the file in 16-bit words.
+    #[serde(default)]
+    file_length: u32,
+    format_version: FormatVersion,
+    /// Offset in 16-bit words from the beginning of the file.
+    #[serde(default)]
+    pointer_to_media_set_pn_length: u32,
+    /// Offset in 16-bit words from the beginning of the file.
+    #[serde(default)]
+    pointer_to_number_of_loads: u32,
+    /// Offset in 16-bit words from the beginning of the file.
+    #[serde(default)]
+    pointer_to_user_defined_data: u32,
+    media_set_pn: PartNumber,
+    /// The supplier of the media set PN, only in the annotated export.
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    media_set_supplier: Option<String>,
+    media_sequence_number: u8,
+    number_of_media_set_members: u8,
+    loads: Vec<LspRepr>,
+    /// User defined data as a hexadecimal string.
+    user_defined_data: Option<String>,
+    /// The decoded user defined data, only in the annotated export.
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    user_defined_data_decoded: Option<DecodedUserData>,
+    #[serde(default)]
+    file_crc: u16,
+}
+#[cfg(feature = "serde")]
+impl From<LoadsLum> for LoadsLumRepr {
+    fn from(value: LoadsLum) -> Self {
+        Self {
+            file_length: value.get_file_length(),
+            format_version: value.get_format_version(),
+            pointer_to_media_set_pn_length: value.get_pointer_to_media_set_pn_length(),
+            pointer_to_number_of_loads: value.get_pointer_to_number_of_loads(),
+            pointer_to_user_defined_data: value.get_pointer_to_user_defined_data(),
+            media_set_pn: value.get_media_set_pn(),
+            media_set_supplier: None,
+            media_sequence_number: value.get_media_sequence_number(),
+            number_of_media_set_members: value.get_number_of_media_set_members(),
+            loads: value.loads.into_iter().map(LspRepr::from).collect(),
+            user_defined_data: value.user_defined_data.as_deref().map(vec16_to_hex),
+            user_defined_data_decoded: None,
+            file_crc: value.file_crc,
+        }
+    }
+}
+#[cfg(feature = "serde")]
+impl serde::Serialize for Annotated<'_, LoadsLum> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut repr = LoadsLumRepr::from(self.file.clone());
+        repr.media_set_supplier = self.supplier(&repr.media_set_pn);
+        for load in &mut repr.loads {
+            load.load_supplier = self.supplier(&load.load_pn);
+        }
+        repr.user_defined_data_decoded
+            .clone_from(&self.user_defined_data_decoded);
+        serde::Serialize::serialize(&repr, serializer)
+    }
+}
+#[cfg(feature = "serde")]
+impl TryFrom<LoadsLumRepr> for LoadsLum {
+    type Error = LoadsLumError;
+
+    /// Rebuilds the file from its structured representation. The lengths,
+    /// pointers and the CRC are always recomputed.
+    fn try_from(value: LoadsLumRepr) -> Result<Self, Self::Error> {
+        let user_defined_data = value
+            .user_defined_data
+            .map(|data| hex_to_vec16(&data).ok_or(LoadsLumError::InvalidHex("user_defined_data")))
+            .transpose()?;
+        let mut loads_lum = LoadsLum {
+            header: FileHeader::new(value.format_version),
+            media_set: MediaSetInfo::new(
+                &value.media_set_pn,
+                value.media_sequence_number,
+                value.number_of_media_set_members,
+            )
+            .ok_or(LoadsLumError::Overflow("media_set_pn"))?,
+            loads: value
+                .loads
+                .into_iter()
+                .map(Lsp::try_from)
+                .collect::<Result<_, _>>()?,
+            user_defined_data,
+            ..LoadsLum::default()
+        };
+        loads_lum.update_layout()?;
+        Ok(loads_lum)
     }
 }
 impl Display for LoadsLum {
-    #[rustfmt::skip]
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.fmt_with(f, &ManufacturerRegistry::empty())
+    }
+}
+impl DisplayWithManufacturers for LoadsLum {
+    #[rustfmt::skip]
+    fn fmt_with(&self, f: &mut Formatter, registry: &ManufacturerRegistry) -> fmt::Result {
         write!(
             f,
             " \
@@ -125,12 +461,12 @@ impl Display for LoadsLum {
             {}\n \
             {} | {} | {} Offsets\n \
             {} Chars in Media Set PN\n \
-            {} Media Set PN\n \
+            {}{}{} Media Set PN\n \
             {} Media number\n \
             {} Total Media sets\n \
             {} Total loads\n \
             {}\n \
-            {:?} User Data\n \
+            {} User Data\n \
             0x{:x} CRC \
             ",
             self.get_file_length() * 2,
@@ -138,16 +474,20 @@ impl Display for LoadsLum {
             self.get_pointer_to_media_set_pn_length(),
             self.get_pointer_to_number_of_loads(),
             self.get_pointer_to_user_defined_data(),
-            self.media_set_pn_length,
+            self.media_set.media_set_pn_length,
             self.get_media_set_pn(),
-            self.media_sequence_number_x,
-            self.number_of_media_set_members_y,
+            supplier_note(registry, &self.get_media_set_pn()),
+            validity_note(&self.get_media_set_pn()),
+            self.get_media_sequence_number(),
+            self.get_number_of_media_set_members(),
             self.number_of_loads,
             self.loads
                 .iter()
-                .map(|f| format!("{f}"))
+                .map(|f| format!("{}", WithManufacturers::new(f, registry)))
                 .fold(String::new(), |acc, arg| acc + arg.as_str()),
-            self.user_defined_data,
+            self.user_defined_data
+                .as_deref()
+                .map_or("None".to_string(), vec16_to_hex),
             self.file_crc
         )
     }
@@ -158,6 +498,7 @@ mod tests {
     use std::path::PathBuf;
 
     use super::*;
+    use crate::field_map::FieldValue;
 
     #[test]
     fn test_simple_loads_lum() {
@@ -168,18 +509,232 @@ mod tests {
         println!("{loads_lum}");
 
         assert_eq!(loads_lum.get_file_length(), 39);
-        assert_eq!(loads_lum.media_file_format_verion, 0x8002);
+        assert_eq!(loads_lum.header.media_file_format_verion, 0x8002);
         assert_eq!(loads_lum.get_pointer_to_media_set_pn_length(), 0x9);
         assert_eq!(loads_lum.get_pointer_to_number_of_loads(), 0x10);
         assert_eq!(loads_lum.get_pointer_to_user_defined_data(), 0x0);
-        assert_eq!(loads_lum.media_set_pn_length, 10);
-        assert_eq!(loads_lum.media_set_pn.len(), 5);
+        assert_eq!(loads_lum.media_set.media_set_pn_length, 10);
+        assert_eq!(loads_lum.media_set.media_set_pn.len(), 5);
         assert_eq!(loads_lum.get_media_set_pn(), "ABCDEFGH12");
-        assert_eq!(loads_lum.media_sequence_number_x, 1);
-        assert_eq!(loads_lum.number_of_media_set_members_y, 1);
+        assert_eq!(loads_lum.media_set.media_sequence_number_x, 1);
+        assert_eq!(loads_lum.media_set.number_of_media_set_members_y, 1);
         assert_eq!(loads_lum.number_of_loads, 1);
         assert_eq!(loads_lum.loads.len(), 1);
         assert_eq!(loads_lum.user_defined_data, None);
         assert_eq!(loads_lum.file_crc, 0x5246);
     }
+
+    #[test]
+    fn test_loads_lum_arinc_file() {
+        let file = PathBuf::from("../test-data/LOADS.LUM");
+        let bytes = std::fs::read(&file).unwrap();
+        let mut loads_lum = LoadsLum::from_bytes(&bytes).unwrap();
+        assert_eq!(loads_lum.get_format_version(), FormatVersion::Arinc665_1);
+        assert_eq!(loads_lum.get_file_type_string(), "Load File");
+        assert_eq!(loads_lum.get_media_sequence_number(), 1);
+        assert_eq!(loads_lum.get_number_of_media_set_members(), 1);
+        assert_eq!(loads_lum.get_user_defined_data(), None);
+        assert_eq!(loads_lum.to_bytes().unwrap(), bytes);
+
+        // The CRC stored in the test file doesn't match its content.
+        assert!(!loads_lum.has_valid_file_crc().unwrap());
+        loads_lum.update_layout().unwrap();
+        assert!(loads_lum.has_valid_file_crc().unwrap());
+        assert_eq!(loads_lum.to_bytes().unwrap()[..76], bytes[..76]);
+
+        for format_version in [
+            FormatVersion::Arinc665_3Load,
+            FormatVersion::Arinc665_3Batch,
+        ] {
+            assert!(matches!(
+                loads_lum.convert(format_version),
+                Err(LoadsLumError::UnsupportedFormatVersion(_))
+            ));
+        }
+        let warnings = loads_lum.convert(FormatVersion::Arinc665_3Media).unwrap();
+        assert_eq!(warnings, vec![]);
+        assert_eq!(loads_lum.get_pointer_to_media_set_pn_length(), 10);
+        assert_eq!(loads_lum.get_file_length(), 40);
+        loads_lum.header.spare = 1;
+        let warnings = loads_lum.convert(FormatVersion::Arinc665_1).unwrap();
+        assert_eq!(warnings[0].field, "spare");
+        assert_eq!(loads_lum.to_bytes().unwrap()[..76], bytes[..76]);
+    }
+
+    #[test]
+    fn test_loads_lum_user_defined_data_pointer() {
+        let mut bytes = std::fs::read("../test-data/LOADS.LUM").unwrap();
+        // pointer_to_user_defined_data, the file CRC is the 39th word.
+        bytes[14..18].copy_from_slice(&38u32.to_be_bytes());
+        let loads_lum = LoadsLum::from_bytes(&bytes).unwrap();
+        assert_eq!(loads_lum.get_user_defined_data(), Some(vec![]));
+        // A pointer past the file CRC is an error, not an overflow.
+        bytes[14..18].copy_from_slice(&39u32.to_be_bytes());
+        assert!(LoadsLum::from_bytes(&bytes).is_err());
+        bytes[14..18].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(LoadsLum::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_loads_lum_field_map() {
+        let file = PathBuf::from("../test-data/LOADS.LUM");
+        let bytes = std::fs::read(&file).unwrap();
+        let loads_lum = LoadsLum::from_bytes(&bytes).unwrap();
+        let fields = loads_lum.get_field_map().unwrap();
+
+        let mut offset = 0;
+        for field in &fields {
+            assert_eq!(field.offset, offset, "{}", field.name);
+            assert_eq!(field.raw, bytes[offset..offset + field.length]);
+            offset += field.length;
+        }
+        assert_eq!(offset, bytes.len());
+
+        assert_eq!(fields[0].name, "file_length");
+        assert_eq!(fields[0].value, FieldValue::Integer(39));
+        assert_eq!(
+            fields[1].value,
+            FieldValue::FormatVersion(FormatVersion::Arinc665_1)
+        );
+        let field = fields
+            .iter()
+            .find(|f| f.name == "loads[0].target_hw_ids[0].target_hw_id")
+            .unwrap();
+        assert_eq!(field.value, FieldValue::Text("TYUIOP".to_string()));
+        assert_eq!(fields.last().unwrap().name, "file_crc");
+        assert_eq!(fields.last().unwrap().value, FieldValue::Integer(0x5246));
+    }
+
+    #[test]
+    fn test_loads_lum_editing() {
+        let file = PathBuf::from("../test-data/LOADS.LUM");
+        let mut loads_lum = LoadsLum::new(file.as_path()).unwrap();
+        // Data from a later revision of the format, unknown to the parser.
+        loads_lum.expansion_point_1 = vec![0xaaaa];
+        loads_lum.expansion_point_3 = vec![0xbbbb, 0xcccc];
+        loads_lum.set_user_defined_data(Some(&[1, 2, 3]));
+        loads_lum.update_layout().unwrap();
+        let mut loads_lum = LoadsLum::from_bytes(&loads_lum.to_bytes().unwrap()).unwrap();
+        assert_eq!(loads_lum.get_pointer_to_media_set_pn_length(), 10);
+        assert_eq!(loads_lum.expansion_point_1, vec![0xaaaa]);
+        assert_eq!(loads_lum.expansion_point_3, vec![0xbbbb, 0xcccc]);
+        assert_eq!(loads_lum.get_user_defined_data(), Some(vec![1, 2, 3, 0]));
+
+        let target_hw_ids = loads_lum.get_loads()[0].get_target_hw_ids().len();
+        let mut load = loads_lum.get_loads()[0].clone();
+        load.set_load_pn(&PartNumber::new("ABC73-1300-1")).unwrap();
+        load.get_target_hw_ids_mut()
+            .push(TargetHW::new("NEW HW").unwrap());
+        loads_lum.get_loads_mut().insert(0, load);
+        loads_lum
+            .set_media_set_pn(&PartNumber::new("ABC7313001"))
+            .unwrap();
+        loads_lum.update_layout().unwrap();
+
+        let edited = LoadsLum::from_bytes(&loads_lum.to_bytes().unwrap()).unwrap();
+        assert_eq!(edited, loads_lum);
+        assert!(edited.has_valid_file_crc().unwrap());
+        assert_eq!(edited.get_media_set_pn(), "ABC7313001");
+        assert_eq!(edited.get_loads().len(), 2);
+        assert_eq!(edited.get_loads()[0].get_load_pn(), "ABC73-1300-1");
+        assert_eq!(
+            edited.get_loads()[0].get_target_hw_ids().len(),
+            target_hw_ids + 1
+        );
+        assert_eq!(edited.expansion_point_1, vec![0xaaaa]);
+        assert_eq!(edited.expansion_point_3, vec![0xbbbb, 0xcccc]);
+        assert_eq!(edited.get_user_defined_data(), Some(vec![1, 2, 3, 0]));
+
+        loads_lum.get_loads_mut().swap(0, 1);
+        loads_lum.get_loads_mut().remove(1);
+        loads_lum.set_user_defined_data(None);
+        loads_lum.update_layout().unwrap();
+        let edited = LoadsLum::from_bytes(&loads_lum.to_bytes().unwrap()).unwrap();
+        assert_eq!(edited.get_loads()[0].get_load_pn(), "ABCDEFGH12");
+        assert_eq!(edited.get_pointer_to_user_defined_data(), 0);
+        assert_eq!(edited.expansion_point_3, vec![0xbbbb, 0xcccc]);
+    }
+
+    #[test]
+    fn test_loads_lum_from_loads() {
+        let media_set = MediaSetInfo::new(&PartNumber::new("ABC1813001"), 1, 2).unwrap();
+        let load = Lsp::new(
+            &PartNumber::new("ABC1813001"),
+            "ABC1813001.LUH",
+            2,
+            vec![TargetHW::new("TYUIOP").unwrap()],
+        )
+        .unwrap();
+        let loads_lum =
+            LoadsLum::from_loads(FormatVersion::Arinc665_2, media_set.clone(), vec![load]).unwrap();
+        let parsed = LoadsLum::from_bytes(&loads_lum.to_bytes().unwrap()).unwrap();
+        assert_eq!(parsed, loads_lum);
+        assert!(parsed.has_valid_file_crc().unwrap());
+        assert_eq!(parsed.get_number_of_media_set_members(), 2);
+        assert_eq!(parsed.get_loads()[0].get_member_sequence_number(), 2);
+
+        assert!(matches!(
+            LoadsLum::from_loads(FormatVersion::Unknown(0x1234), media_set, Vec::new()),
+            Err(LoadsLumError::UnsupportedFormatVersion(_))
+        ));
+    }
+
+    #[test]
+    fn test_loads_lum_display_with_manufacturers() {
+        let file = PathBuf::from("../test-data/LOADS.LUM");
+        let loads_lum = LoadsLum::new(file.as_path()).unwrap();
+        let mut registry = ManufacturerRegistry::empty();
+        registry.insert("ABC", "Alpha Beta Corp");
+
+        let output = WithManufacturers::new(&loads_lum, &registry).to_string();
+        assert_eq!(output.matches("ABCDEFGH12 [Alpha Beta Corp]").count(), 2);
+        assert!(!loads_lum.to_string().contains("Alpha Beta Corp"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_loads_lum_serde() {
+        let file = PathBuf::from("../test-data/LOADS.LUM");
+        let loads_lum = LoadsLum::new(file.as_path()).unwrap();
+
+        let json = serde_json::to_value(&loads_lum).unwrap();
+        assert_eq!(json["format_version"], "ARINC665-1");
+        assert_eq!(json["file_length"], 39);
+        assert_eq!(json["pointer_to_number_of_loads"], 0x10);
+        assert_eq!(json["media_set_pn"], "ABCDEFGH12");
+        assert_eq!(json["loads"][0]["load_pn"], "ABCDEFGH12");
+        assert_eq!(json["loads"][0]["target_hw_ids"][0], "TYUIOP");
+        assert!(json.get("file_length_msb").is_none());
+        assert!(json.get("media_set_supplier").is_none());
+        assert!(json["loads"][0].get("load_supplier").is_none());
+
+        let mut registry = ManufacturerRegistry::default();
+        registry.extend_from_str("ABC,Alpha Beta Corp").unwrap();
+        let decoded = Some(DecodedUserData::Text("KEY=VALUE".to_string()));
+        let annotated =
+            serde_json::to_value(Annotated::new(&loads_lum, &registry, decoded)).unwrap();
+        assert_eq!(annotated["media_set_supplier"], "Alpha Beta Corp");
+        assert_eq!(annotated["loads"][0]["load_supplier"], "Alpha Beta Corp");
+        assert_eq!(annotated["user_defined_data_decoded"]["text"], "KEY=VALUE");
+        // The annotations are ignored when deserializing.
+        let rebuilt: LoadsLum = serde_json::from_value(annotated).unwrap();
+        assert_eq!(rebuilt.get_media_set_pn(), loads_lum.get_media_set_pn());
+
+        // The CRC of the test file doesn't match its content, everything else
+        // is rebuilt byte for byte.
+        let rebuilt: LoadsLum = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            rebuilt,
+            LoadsLum {
+                file_crc: rebuilt.file_crc,
+                ..loads_lum
+            }
+        );
+        assert!(rebuilt.has_valid_file_crc().unwrap());
+        let buf = rebuilt.to_bytes().unwrap();
+        assert_eq!(
+            buf[..buf.len() - 2],
+            std::fs::read(file).unwrap()[..buf.len() - 2]
+        );
+    }
 }
@@ -1,12 +1,24 @@
-use std::fmt::{self, Display, Formatter};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
 
 use binrw::binrw;
 
-use crate::utils::vec16_to_string;
+use crate::arinc_string::{push_issues, ArincString, Charset, StringIssue};
+use crate::error::LoadsLumError;
+use crate::field_map::FieldReader;
+use crate::utils::{string_to_vec16, vec16_to_string};
 
 #[binrw]
 #[brw(big)]
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "String", try_from = "String")
+)]
 pub struct TargetHW {
     target_hw_id_length: u16, // number of chars
 
@@ -14,11 +26,60 @@ pub struct TargetHW {
     target_hw_id: Vec<u16>,
 }
 impl TargetHW {
+    /// Constructs a new [`TargetHW`] storing `target_hw_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the ID is longer than the format allows.
+    pub fn new(target_hw_id: &str) -> Result<Self, LoadsLumError> {
+        Ok(Self {
+            target_hw_id_length: u16::try_from(target_hw_id.len())
+                .map_err(|_| LoadsLumError::Overflow("target_hw_id"))?,
+            target_hw_id: string_to_vec16(target_hw_id),
+        })
+    }
+
+    // Returns the size of the entry in 16-bit words
+    pub(crate) fn get_size(&self) -> usize {
+        1 + self.target_hw_id.len()
+    }
+
+    pub(crate) fn read_fields(reader: &mut FieldReader) {
+        reader.string_with_length("target_hw_id");
+    }
+
+    #[must_use]
     pub fn get_target_hw_id(&self) -> String {
         // If target_hw_id_length is odd, an extra NUL byte is added at the end.
         // This function removes the NUL byte if it exists.
         vec16_to_string(&self.target_hw_id, self.target_hw_id_length as usize)
     }
+
+    #[must_use]
+    pub fn get_target_hw_id_raw(&self) -> ArincString {
+        ArincString::new(&self.target_hw_id, self.target_hw_id_length as usize)
+    }
+
+    pub(crate) fn push_string_issues(&self, issues: &mut Vec<StringIssue>, prefix: &str) {
+        push_issues(
+            issues,
+            &format!("{prefix}.target_hw_id"),
+            &self.get_target_hw_id_raw(),
+            Charset::Text,
+        );
+    }
+}
+impl From<TargetHW> for String {
+    fn from(value: TargetHW) -> Self {
+        value.get_target_hw_id()
+    }
+}
+impl TryFrom<String> for TargetHW {
+    type Error = LoadsLumError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        TargetHW::new(&value)
+    }
 }
 impl Display for TargetHW {
     #[rustfmt::skip]
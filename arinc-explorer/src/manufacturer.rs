@@ -0,0 +1,181 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::fs;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use crate::error::ManufacturerError;
+use crate::part_number::PartNumber;
+
+/// Lookup table from ARINC 665 manufacturer codes to supplier names.
+///
+/// The library doesn't ship any manufacturer code, the table is filled with
+/// user-supplied entries in the `MMM,Supplier name` format.
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct ManufacturerRegistry {
+    suppliers: BTreeMap<String, String>,
+}
+impl ManufacturerRegistry {
+    /// Constructs a registry without any manufacturer code.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            suppliers: BTreeMap::new(),
+        }
+    }
+
+    /// Adds or replaces the supplier name of a manufacturer code.
+    pub fn insert(&mut self, code: impl Into<String>, supplier: impl Into<String>) {
+        self.suppliers.insert(code.into(), supplier.into());
+    }
+
+    /// Returns the supplier name of a manufacturer code.
+    #[must_use]
+    pub fn get(&self, code: &str) -> Option<&str> {
+        self.suppliers.get(code).map(String::as_str)
+    }
+
+    /// Returns the supplier name of the manufacturer code of a part number.
+    #[must_use]
+    pub fn get_supplier(&self, part_number: &PartNumber) -> Option<&str> {
+        self.get(&part_number.get_manufacturer_code()?)
+    }
+
+    /// Extends the registry with the entries in `contents`.
+    ///
+    /// Each line is either empty, a comment starting with `#` or a
+    /// `MMM,Supplier name` entry. Entries replace the supplier name of codes
+    /// already in the registry.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if a line is not a valid entry. The registry is left
+    /// unchanged in this case.
+    pub fn extend_from_str(&mut self, contents: &str) -> Result<(), ManufacturerError> {
+        let mut entries = Vec::new();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((code, supplier)) = line.split_once(',') else {
+                return Err(ManufacturerError::InvalidLine {
+                    line: index + 1,
+                    content: line.to_string(),
+                });
+            };
+            let (code, supplier) = (code.trim(), supplier.trim());
+            if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(ManufacturerError::InvalidCode {
+                    line: index + 1,
+                    code: code.to_string(),
+                });
+            }
+            entries.push((code.to_ascii_uppercase(), supplier.to_string()));
+        }
+        self.suppliers.extend(entries);
+        Ok(())
+    }
+
+    /// Extends the registry with the entries of a user-supplied file.
+    ///
+    /// # Arguments
+    ///
+    /// - `path`: the path to a file in the format accepted by
+    ///   [`ManufacturerRegistry::extend_from_str`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the file can't be read or if it contains an invalid
+    /// entry.
+    #[cfg(feature = "std")]
+    pub fn extend_from_file(&mut self, path: &Path) -> Result<(), ManufacturerError> {
+        let contents = fs::read_to_string(path)?;
+        self.extend_from_str(&contents)
+    }
+
+    /// Returns the number of manufacturer codes in the registry.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.suppliers.len()
+    }
+
+    /// Returns `true` if the registry doesn't contain any manufacturer code.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.suppliers.is_empty()
+    }
+}
+
+/// Types whose [`Display`] output contains part numbers which can be
+/// annotated with supplier names.
+pub trait DisplayWithManufacturers {
+    /// Formats the value, resolving manufacturer codes with `registry`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the formatter fails.
+    fn fmt_with(&self, f: &mut Formatter, registry: &ManufacturerRegistry) -> fmt::Result;
+}
+
+/// A [`Display`] adapter which shows the supplier name next to each part
+/// number, see [`DisplayWithManufacturers`].
+pub struct WithManufacturers<'a, T: ?Sized> {
+    value: &'a T,
+    registry: &'a ManufacturerRegistry,
+}
+impl<'a, T: DisplayWithManufacturers + ?Sized> WithManufacturers<'a, T> {
+    pub fn new(value: &'a T, registry: &'a ManufacturerRegistry) -> Self {
+        Self { value, registry }
+    }
+}
+impl<T: DisplayWithManufacturers + ?Sized> Display for WithManufacturers<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.value.fmt_with(f, self.registry)
+    }
+}
+
+/// Returns a short annotation with the supplier name of a part number, or an
+/// empty string if the manufacturer code is unknown.
+pub(crate) fn supplier_note(registry: &ManufacturerRegistry, part_number: &PartNumber) -> String {
+    match registry.get_supplier(part_number) {
+        Some(supplier) => format!(" [{supplier}]"),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manufacturer_registry() {
+        let mut registry = ManufacturerRegistry::default();
+        assert!(registry.is_empty());
+        registry
+            .extend_from_str("# Test codes\n\nABC, Alpha Beta Corp\nxyz,Xyz Avionics\n")
+            .unwrap();
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.get("ABC"), Some("Alpha Beta Corp"));
+        assert_eq!(registry.get("XYZ"), Some("Xyz Avionics"));
+        assert_eq!(
+            registry.get_supplier(&PartNumber::new("ABC1813001")),
+            Some("Alpha Beta Corp")
+        );
+        assert_eq!(registry.get_supplier(&PartNumber::new("QQQ1813001")), None);
+
+        assert!(matches!(
+            registry.extend_from_str("DEF,Delta\nGHI Gamma"),
+            Err(ManufacturerError::InvalidLine { line: 2, .. })
+        ));
+        assert!(matches!(
+            registry.extend_from_str("ABCD,Too long"),
+            Err(ManufacturerError::InvalidCode { line: 1, .. })
+        ));
+        assert_eq!(registry.get("DEF"), None);
+    }
+}
@@ -0,0 +1,144 @@
+//! The media of a media set, as a directory with the `LOADS.LUM` and
+//! `FILES.LUM` files at its root.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::crc::crc16;
+use crate::error::MediaSetError;
+use crate::files::{File, FilesLum};
+use crate::loads::LoadsLum;
+
+#[derive(Debug, Clone)]
+pub struct MediaSet {
+    root: PathBuf,
+    loads_lum: LoadsLum,
+    files_lum: FilesLum,
+}
+impl MediaSet {
+    /// Opens the media whose root directory is `root`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if `LOADS.LUM` or `FILES.LUM` are missing or can't be
+    /// parsed.
+    pub fn open(root: &Path) -> Result<Self, MediaSetError> {
+        Ok(Self {
+            root: root.to_path_buf(),
+            loads_lum: LoadsLum::new(&root.join("LOADS.LUM"))?,
+            files_lum: FilesLum::new(&root.join("FILES.LUM"))?,
+        })
+    }
+
+    /// Opens the media whose root directory is `root` without blocking the
+    /// executor.
+    ///
+    /// # Errors
+    ///
+    /// See [`MediaSet::open`].
+    #[cfg(feature = "async")]
+    pub async fn open_async(root: &Path) -> Result<Self, MediaSetError> {
+        Ok(Self {
+            root: root.to_path_buf(),
+            loads_lum: LoadsLum::new_async(&root.join("LOADS.LUM")).await?,
+            files_lum: FilesLum::new_async(&root.join("FILES.LUM")).await?,
+        })
+    }
+
+    #[must_use]
+    pub fn get_root(&self) -> &Path {
+        &self.root
+    }
+
+    #[must_use]
+    pub fn get_loads_lum(&self) -> &LoadsLum {
+        &self.loads_lum
+    }
+
+    #[must_use]
+    pub fn get_files_lum(&self) -> &FilesLum {
+        &self.files_lum
+    }
+
+    /// Returns the location of a file listed in `FILES.LUM`, see
+    /// [`get_relative_path`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the pathname or the name would escape the media
+    /// root.
+    pub fn get_file_path(&self, file: &File) -> Result<PathBuf, MediaSetError> {
+        Ok(self.root.join(get_relative_path(file)?))
+    }
+
+    /// Returns `true` if the CRC of the content of `file` matches the one
+    /// listed in `FILES.LUM`, as well as its check value if it has one. A
+    /// check value of an unsupported type is not verified, see
+    /// [`File::has_valid_check_value`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the file is outside the media root or it can't be
+    /// read.
+    pub fn has_valid_file_crc(&self, file: &File) -> Result<bool, MediaSetError> {
+        let content = fs::read(self.get_file_path(file)?)?;
+        Ok(crc16(&content) == file.get_file_crc()
+            && file.has_valid_check_value(&content) != Ok(Some(false)))
+    }
+}
+
+/// Returns the path of a file listed in `FILES.LUM` relative to the media
+/// root.
+///
+/// The pathname components are separated by `\` or `/` and they are
+/// relative to the media root, even when the pathname starts with a
+/// separator.
+///
+/// # Errors
+///
+/// Returns [`Err`] if the pathname or the name would escape the media root,
+/// e.g. with a `..` component or a drive letter.
+pub fn get_relative_path(file: &File) -> Result<PathBuf, MediaSetError> {
+    let mut path = PathBuf::new();
+    for component in file.get_file_pathname().split(['\\', '/']) {
+        match component {
+            "" | "." => {}
+            x if x == ".." || x.contains(':') => {
+                return Err(MediaSetError::UnsafePath(file.get_file_pathname()));
+            }
+            x => path.push(x),
+        }
+    }
+    let name = file.get_file_name();
+    if matches!(name.as_str(), "" | "." | "..") || name.contains(['\\', '/', ':']) {
+        return Err(MediaSetError::UnsafePath(name));
+    }
+    path.push(name);
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_media_set() {
+        let media_set = MediaSet::open(Path::new("../test-data")).unwrap();
+        assert_eq!(media_set.get_loads_lum().get_loads().len(), 1);
+
+        let files = media_set.get_files_lum().get_media_set_files();
+        assert_eq!(
+            media_set.get_file_path(&files[0]).unwrap(),
+            Path::new("../test-data/ABC1813001/ABC1813001.LUH")
+        );
+        let mut file = File::new("FILE.BIN", "\\..\\", 1, 0).unwrap();
+        assert!(media_set.get_file_path(&file).is_err());
+        file.set_file_pathname("C:\\DIR\\").unwrap();
+        assert!(get_relative_path(&file).is_err());
+        file.set_file_pathname("\\DIR\\").unwrap();
+        file.set_file_name("..").unwrap();
+        assert!(media_set.get_file_path(&file).is_err());
+        // The loads aren't part of the test data.
+        assert!(media_set.has_valid_file_crc(&files[0]).is_err());
+    }
+}
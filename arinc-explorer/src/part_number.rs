@@ -0,0 +1,245 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+
+use crate::error::PartNumberError;
+
+const MANUFACTURER_CODE_LENGTH: usize = 3;
+const CHECK_CHARACTERS_LENGTH: usize = 2;
+const MAX_PRODUCT_IDENTIFIER_LENGTH: usize = 8;
+
+/// An ARINC 665 part number (`MMMCC-SSSS-SSSS`).
+///
+/// The part number is made of a three characters manufacturer code (`MMM`),
+/// two hexadecimal check characters (`CC`) and a product specific identifier
+/// of up to eight characters (`SSSS-SSSS`). The dashes are only a presentation
+/// aid and they are ignored when parsing the structure.
+///
+/// The raw string read from the file is always kept, even if it doesn't
+/// follow the ARINC 665 structure, so that malformed part numbers can still
+/// be displayed and diagnosed with [`PartNumber::validate`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
+pub struct PartNumber {
+    raw: String,
+}
+impl PartNumber {
+    /// Constructs a new [`PartNumber`] without validating it.
+    #[must_use]
+    pub fn new(raw: impl Into<String>) -> Self {
+        Self { raw: raw.into() }
+    }
+
+    /// Returns the part number as it is stored in the file.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    // Returns the part number characters without the presentation dashes.
+    fn compact(&self) -> String {
+        self.raw.chars().filter(|c| *c != '-').collect()
+    }
+
+    /// Returns the manufacturer code (`MMM`), if the part number is long
+    /// enough to contain one.
+    #[must_use]
+    pub fn get_manufacturer_code(&self) -> Option<String> {
+        let compact = self.compact();
+        compact.get(..MANUFACTURER_CODE_LENGTH).map(str::to_string)
+    }
+
+    /// Returns the check characters (`CC`) stored in the part number.
+    #[must_use]
+    pub fn get_check_characters(&self) -> Option<String> {
+        let compact = self.compact();
+        compact
+            .get(MANUFACTURER_CODE_LENGTH..MANUFACTURER_CODE_LENGTH + CHECK_CHARACTERS_LENGTH)
+            .map(str::to_string)
+    }
+
+    /// Returns the product specific identifier (`SSSS-SSSS`) without dashes.
+    #[must_use]
+    pub fn get_product_identifier(&self) -> Option<String> {
+        let compact = self.compact();
+        compact
+            .get(MANUFACTURER_CODE_LENGTH + CHECK_CHARACTERS_LENGTH..)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    }
+
+    /// Computes the check characters from the manufacturer code and the
+    /// product identifier.
+    ///
+    /// The check characters are the 8-bit exclusive OR of the ASCII codes of
+    /// the `MMM` and `SSSS-SSSS` characters, written as two upper case
+    /// hexadecimal digits.
+    #[must_use]
+    pub fn compute_check_characters(&self) -> Option<String> {
+        let manufacturer_code = self.get_manufacturer_code()?;
+        let product_identifier = self.get_product_identifier()?;
+        let check = manufacturer_code
+            .bytes()
+            .chain(product_identifier.bytes())
+            .fold(0u8, |acc, b| acc ^ b);
+        Some(format!("{check:02X}"))
+    }
+
+    /// Validates the structure and the check characters of the part number.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] describing the first problem found in the part number.
+    pub fn validate(&self) -> Result<(), PartNumberError> {
+        let compact = self.compact();
+        if let Some(character) = compact
+            .chars()
+            .find(|c| !(c.is_ascii_uppercase() || c.is_ascii_digit()))
+        {
+            return Err(PartNumberError::InvalidCharacter(character));
+        }
+
+        let (Some(_), Some(found), Some(product_identifier)) = (
+            self.get_manufacturer_code(),
+            self.get_check_characters(),
+            self.get_product_identifier(),
+        ) else {
+            return Err(PartNumberError::TooShort(compact.len()));
+        };
+        if product_identifier.len() > MAX_PRODUCT_IDENTIFIER_LENGTH {
+            return Err(PartNumberError::TooLong(compact.len()));
+        }
+        if !found.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(PartNumberError::InvalidCheckCharacters(found));
+        }
+
+        let expected = self.compute_check_characters().unwrap_or_default();
+        if found != expected {
+            return Err(PartNumberError::CheckCharactersMismatch { found, expected });
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the part number passes [`PartNumber::validate`].
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_ok()
+    }
+}
+impl FromStr for PartNumber {
+    type Err = PartNumberError;
+
+    /// Parses and validates a part number.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let part_number = PartNumber::new(s);
+        part_number.validate()?;
+        Ok(part_number)
+    }
+}
+impl Display for PartNumber {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+impl PartialEq<&str> for PartNumber {
+    fn eq(&self, other: &&str) -> bool {
+        self.raw == *other
+    }
+}
+impl PartialEq<str> for PartNumber {
+    fn eq(&self, other: &str) -> bool {
+        self.raw == other
+    }
+}
+#[cfg(feature = "serde")]
+impl serde::Serialize for PartNumber {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.raw)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PartNumber {
+    /// Deserializes a part number without validating it, like the ones read
+    /// from the files.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(PartNumber::new)
+    }
+}
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for PartNumber {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "PartNumber".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": "string",
+            "description": "ARINC 665 part number (MMMCC-SSSS-SSSS) as stored in the file.",
+        })
+    }
+}
+
+/// Returns a short annotation for [`Display`] implementations when the part
+/// number is not valid.
+pub(crate) fn validity_note(part_number: &PartNumber) -> String {
+    match part_number.validate() {
+        Ok(()) => String::new(),
+        Err(e) => format!(" (invalid: {e})"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_number_structure() {
+        let pn = PartNumber::new("ABC73-1300-1");
+        assert_eq!(pn.get_manufacturer_code().unwrap(), "ABC");
+        assert_eq!(pn.get_check_characters().unwrap(), "73");
+        assert_eq!(pn.get_product_identifier().unwrap(), "13001");
+        assert_eq!(pn.compute_check_characters().unwrap(), "73");
+        assert!(pn.is_valid());
+        assert_eq!(pn.as_str(), "ABC73-1300-1");
+
+        let pn: PartNumber = "ABC7313001".parse().unwrap();
+        assert_eq!(pn, "ABC7313001");
+    }
+
+    #[test]
+    fn test_part_number_validation() {
+        let pn = PartNumber::new("ABC1813001");
+        assert!(matches!(
+            pn.validate(),
+            Err(PartNumberError::CheckCharactersMismatch { ref found, ref expected })
+                if found == "18" && expected == "73"
+        ));
+
+        let pn = PartNumber::new("ABCDEFGH12");
+        assert!(matches!(
+            pn.validate(),
+            Err(PartNumberError::CheckCharactersMismatch { ref found, ref expected })
+                if found == "DE" && expected == "0A"
+        ));
+
+        let pn = PartNumber::new("ABCXY13001");
+        assert!(matches!(
+            pn.validate(),
+            Err(PartNumberError::InvalidCheckCharacters(ref found)) if found == "XY"
+        ));
+
+        assert!(matches!(
+            "ABC7".parse::<PartNumber>(),
+            Err(PartNumberError::TooShort(4))
+        ));
+        assert!(matches!(
+            "ABC00123456789".parse::<PartNumber>(),
+            Err(PartNumberError::TooLong(14))
+        ));
+        assert!(matches!(
+            "abc7313001".parse::<PartNumber>(),
+            Err(PartNumberError::InvalidCharacter('a'))
+        ));
+        assert_eq!(PartNumber::new("AB").get_check_characters(), None);
+    }
+}
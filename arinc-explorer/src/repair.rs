@@ -0,0 +1,379 @@
+//! Lenient reading of damaged `LOADS.LUM` and `FILES.LUM` files.
+//!
+//! The entries are found with their counts, string lengths and pointers to
+//! the next entry. The other pointers and the file length are recomputed, as
+//! well as the `file_crc`. A string length is corrected when it disagrees
+//! with the padding of its last word, e.g. after a character was typed over
+//! the padding byte.
+//!
+//! The words between the fields of an entry and the next entry are kept, e.g.
+//! the check value of a file in an ARINC 665-3 `FILES.LUM`. A pointer to the
+//! next entry which leads back into the entry or past the file can't be told
+//! apart from such words, hence the file isn't repaired. The last entry of an
+//! ARINC 665-3 `FILES.LUM` ends at the user defined data, the check value or
+//! the file CRC, like in [`FilesLum`].
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use crate::arinc_file::ArincFile;
+use crate::error::RepairError;
+use crate::field_map::FieldValue;
+use crate::files::FilesLum;
+use crate::format_version::FormatVersion;
+use crate::loads::LoadsLum;
+use crate::utils::{combine_words, split_words, vec16_to_bytes, vec16_to_hex};
+
+/// A value changed by [`repair_loads_lum`] or [`repair_files_lum`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RepairedValue {
+    /// The field path, as in the field map of the file.
+    pub field: String,
+    /// Offset in bytes from the beginning of the file.
+    pub offset: usize,
+    pub old: String,
+    pub new: String,
+}
+impl Display for RepairedValue {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} at 0x{:08x}: {} -> {}", self.field, self.offset, self.old, self.new)
+    }
+}
+
+// The words of the file being walked.
+struct Words(Vec<u16>);
+impl Words {
+    fn get(&self, position: usize) -> Result<u16, RepairError> {
+        self.0
+            .get(position)
+            .copied()
+            .ok_or(RepairError::Truncated((position + 1) * 2))
+    }
+
+    fn set(&mut self, position: usize, value: u16) -> Result<(), RepairError> {
+        let word = self
+            .0
+            .get_mut(position)
+            .ok_or(RepairError::Truncated((position + 1) * 2))?;
+        *word = value;
+        Ok(())
+    }
+
+    fn set_u32(&mut self, position: usize, value: usize) -> Result<(), RepairError> {
+        let value = u32::try_from(value).map_err(|_| RepairError::Truncated(usize::MAX))?;
+        let (msb, lsb) = split_words(value);
+        self.set(position, msb)?;
+        self.set(position + 1, lsb)
+    }
+
+    // Sets the file length and returns the position of the first pointer.
+    fn header(&mut self) -> Result<usize, RepairError> {
+        self.set_u32(0, self.0.len())?;
+        // ARINC 665-1 files don't have the spare word.
+        Ok(if self.get(2)? == u16::from(FormatVersion::Arinc665_1) {
+            3
+        } else {
+            4
+        })
+    }
+
+    // Corrects the length of the string at `position` to the characters
+    // before the padding of its last word and returns the position after it.
+    fn string(&mut self, position: usize) -> Result<usize, RepairError> {
+        let words = usize::from(self.get(position)?).div_ceil(2);
+        if words > 0 {
+            let last = self.get(position + words)?;
+            let length = if last.to_be_bytes()[1] == 0 {
+                words * 2 - 1
+            } else {
+                words * 2
+            };
+            self.set(position, u16::try_from(length).unwrap_or(u16::MAX))?;
+        }
+        Ok(position + 1 + words)
+    }
+
+    // Walks the media set PN, sequence number and number of members.
+    fn media_set(&mut self, position: usize) -> Result<usize, RepairError> {
+        Ok(self.string(position)? + 1)
+    }
+
+    // Returns the position of the entry after the one starting at `start`,
+    // whose fields end at `end`. The pointer of the last entry is cleared.
+    fn next_entry(&mut self, start: usize, end: usize, last: bool) -> Result<usize, RepairError> {
+        if last {
+            self.set(start, 0)?;
+            return Ok(end);
+        }
+        let next = start + usize::from(self.get(start)?);
+        if next < end || next + 1 >= self.0.len() {
+            return Err(RepairError::InvalidPointer(start * 2));
+        }
+        Ok(next)
+    }
+
+    // Returns the position of the file CRC, after `end`.
+    fn file_crc(&self, end: usize) -> Result<usize, RepairError> {
+        let file_crc = self.0.len().saturating_sub(1);
+        if end > file_crc {
+            return Err(RepairError::Truncated((end + 1) * 2));
+        }
+        Ok(file_crc)
+    }
+}
+
+fn normalize_loads_lum(words: &mut Words) -> Result<(), RepairError> {
+    let pointers = words.header()?;
+    let media_set = pointers + 6;
+    words.set_u32(pointers, media_set)?;
+    let mut position = words.media_set(media_set)?;
+    let number_of_loads = words.get(position)?;
+    position += 1;
+    for i in 0..number_of_loads {
+        let start = position;
+        position = words.string(start + 1)?;
+        position = words.string(position)?;
+        let number_of_target_hw_ids = words.get(position + 1)?;
+        position += 2;
+        for _ in 0..number_of_target_hw_ids {
+            position = words.string(position)?;
+        }
+        position = words.next_entry(start, position, i + 1 == number_of_loads)?;
+    }
+    let file_crc = words.file_crc(position)?;
+    let user_defined_data = if position < file_crc { position } else { 0 };
+    words.set_u32(pointers + 4, user_defined_data)
+}
+
+fn normalize_files_lum(words: &mut Words) -> Result<(), RepairError> {
+    let pointers = words.header()?;
+    let has_check_value = pointers == 4;
+    let media_set = pointers + if has_check_value { 8 } else { 6 };
+    words.set_u32(pointers, media_set)?;
+    let mut position = words.media_set(media_set)?;
+    let number_of_files = words.get(position)?;
+    position += 1;
+    for i in 0..number_of_files {
+        let start = position;
+        position = words.string(start + 1)?;
+        position = words.string(position)?;
+        // member_sequence_number and file_crc
+        position += 2;
+        position = words.next_entry(start, position, i + 1 == number_of_files)?;
+    }
+    let file_crc = words.file_crc(position)?;
+
+    // The check value is kept if its pointer leads to a check value ending
+    // right before the file CRC.
+    let mut end = file_crc;
+    if has_check_value {
+        let stored = usize::try_from(combine_words(
+            words.get(pointers + 6)?,
+            words.get(pointers + 7)?,
+        ))
+        .unwrap_or(usize::MAX);
+        let fits = stored >= position
+            && stored + 1 < file_crc
+            && stored + 2 + usize::from(words.get(stored)?).div_ceil(2) == file_crc;
+        let check_value = if fits { stored } else { 0 };
+        words.set_u32(pointers + 6, check_value)?;
+        if fits {
+            end = stored;
+        }
+    }
+    // The check value of the last file runs up to the user defined data.
+    if words.get(2)? == u16::from(FormatVersion::Arinc665_3Media) {
+        let stored = usize::try_from(combine_words(
+            words.get(pointers + 4)?,
+            words.get(pointers + 5)?,
+        ))
+        .unwrap_or(usize::MAX);
+        position = match stored {
+            0 => end,
+            x if (position..end).contains(&x) => x,
+            _ => return Err(RepairError::InvalidPointer((pointers + 4) * 2)),
+        };
+    }
+    let user_defined_data = if position < end { position } else { 0 };
+    words.set_u32(pointers + 4, user_defined_data)
+}
+
+fn old_value(new: &FieldValue, raw: &[u8]) -> String {
+    match new {
+        FieldValue::Integer(_) => raw
+            .iter()
+            .fold(0u32, |value, x| value << 8 | u32::from(*x))
+            .to_string(),
+        FieldValue::FormatVersion(_) => {
+            FormatVersion::from(u16::from_be_bytes([raw[0], raw[1]])).to_string()
+        }
+        FieldValue::Text(_) | FieldValue::Bytes(_) => vec16_to_hex(
+            &raw.chunks(2)
+                .map(|x| u16::from_be_bytes([x[0], x[1]]))
+                .collect::<Vec<_>>(),
+        ),
+    }
+}
+
+fn repair<T: ArincFile>(
+    bytes: &[u8],
+    normalize: fn(&mut Words) -> Result<(), RepairError>,
+) -> Result<(T, Vec<RepairedValue>), RepairError>
+where
+    RepairError: From<T::Error>,
+{
+    if bytes.len() % 2 != 0 {
+        return Err(RepairError::OddLength);
+    }
+    let mut words = Words(
+        bytes
+            .chunks(2)
+            .map(|x| u16::from_be_bytes([x[0], x[1]]))
+            .collect(),
+    );
+    normalize(&mut words)?;
+    let mut file = T::from_bytes(&vec16_to_bytes(&words.0))?;
+    file.update_layout()?;
+
+    let changes = file
+        .get_field_map()?
+        .into_iter()
+        .filter_map(|field| {
+            let old = bytes.get(field.offset..field.offset + field.length)?;
+            (old != field.raw).then(|| RepairedValue {
+                old: old_value(&field.value, old),
+                new: field.value.to_string(),
+                field: field.name,
+                offset: field.offset,
+            })
+        })
+        .collect();
+    Ok((file, changes))
+}
+
+/// Reads a damaged `LOADS.LUM` and recomputes its file length, pointers,
+/// string lengths and `file_crc`. Returns the repaired file with the values
+/// which changed.
+///
+/// # Errors
+///
+/// Returns [`Err`] if the counts and string lengths lead past the end of the
+/// file or if the result can't be parsed.
+pub fn repair_loads_lum(bytes: &[u8]) -> Result<(LoadsLum, Vec<RepairedValue>), RepairError> {
+    repair(bytes, normalize_loads_lum)
+}
+
+/// Reads a damaged `FILES.LUM` and recomputes its file length, pointers,
+/// string lengths and `file_crc`, see [`repair_loads_lum`].
+///
+/// # Errors
+///
+/// Returns [`Err`] if the counts and string lengths lead past the end of the
+/// file or if the result can't be parsed.
+pub fn repair_files_lum(bytes: &[u8]) -> Result<(FilesLum, Vec<RepairedValue>), RepairError> {
+    repair(bytes, normalize_files_lum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check_value::CheckValueType;
+    use crate::files::File;
+    use crate::header::MediaSetInfo;
+    use crate::part_number::PartNumber;
+
+    #[test]
+    fn test_repair_loads_lum() {
+        let original = include_bytes!("../../test-data/LOADS.LUM");
+        let (repaired, changes) = repair_loads_lum(original).unwrap();
+        assert!(repaired.has_valid_file_crc().unwrap());
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "file_crc");
+        let (_, changes) = repair_loads_lum(&repaired.to_bytes().unwrap()).unwrap();
+        assert!(changes.is_empty());
+
+        let mut damaged = repaired.to_bytes().unwrap();
+        // file_length, pointer_to_number_of_loads and the media set PN
+        // length, which is 10 characters. The file CRC is the one of the
+        // repaired content.
+        damaged[3] = 0x99;
+        damaged[13] = 0x01;
+        damaged[19] = 9;
+        let (fixed, changes) = repair_loads_lum(&damaged).unwrap();
+        assert_eq!(fixed, repaired);
+        assert_eq!(
+            changes.iter().map(|x| x.field.as_str()).collect::<Vec<_>>(),
+            [
+                "file_length",
+                "pointer_to_number_of_loads",
+                "media_set_pn_length"
+            ]
+        );
+        assert_eq!(
+            changes[2].to_string(),
+            "media_set_pn_length at 0x00000012: 9 -> 10"
+        );
+
+        assert!(matches!(
+            repair_loads_lum(&damaged[..30]),
+            Err(RepairError::Truncated(_))
+        ));
+    }
+
+    #[test]
+    fn test_repair_files_lum() {
+        let original = include_bytes!("../../test-data/FILES.LUM");
+        let (repaired, _) = repair_files_lum(original).unwrap();
+        let mut damaged = repaired.to_bytes().unwrap();
+        // The pointer to the second file entry.
+        let pointer = repaired
+            .get_field_map()
+            .unwrap()
+            .into_iter()
+            .find(|x| x.name == "media_set_files[0].file_pointer")
+            .unwrap()
+            .offset;
+        // A pointer into the entry itself.
+        damaged[pointer] = 0;
+        damaged[pointer + 1] = 0x01;
+        assert!(matches!(
+            repair_files_lum(&damaged),
+            Err(RepairError::InvalidPointer(x)) if x == pointer
+        ));
+        // A pointer past the file.
+        damaged[pointer] = 0xff;
+        assert!(matches!(
+            repair_files_lum(&damaged),
+            Err(RepairError::InvalidPointer(_))
+        ));
+    }
+
+    #[test]
+    fn test_repair_files_lum_check_values() {
+        let media_set = MediaSetInfo::new(&PartNumber::new("ABC1813001"), 1, 1).unwrap();
+        let mut files = vec![
+            File::new("A.BIN", "\\", 1, 0x1234).unwrap(),
+            File::new("B.BIN", "\\", 1, 0x5678).unwrap(),
+        ];
+        files[0]
+            .set_check_value(CheckValueType::Crc32, &[1, 2, 3, 4])
+            .unwrap();
+        files[1]
+            .set_check_value(CheckValueType::Crc16, &[5, 6])
+            .unwrap();
+        let files_lum =
+            FilesLum::from_files(FormatVersion::Arinc665_3Media, media_set, files).unwrap();
+        let bytes = files_lum.to_bytes().unwrap();
+
+        let (repaired, changes) = repair_files_lum(&bytes).unwrap();
+        assert!(changes.is_empty(), "{changes:?}");
+        assert_eq!(repaired, files_lum);
+        assert_eq!(
+            repaired.get_media_set_files()[1].get_check_value(),
+            Some((CheckValueType::Crc16, vec![5, 6]))
+        );
+    }
+}
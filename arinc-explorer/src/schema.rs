@@ -0,0 +1,141 @@
+//! Versioned JSON Schemas of the structured representation produced by the
+//! `serde` feature.
+//!
+//! The schemas describe the serialized form of the files and they are shipped
+//! with the crate in the `schema` directory. [`SCHEMA_VERSION`] must be
+//! increased, and a new directory created, whenever a change to the model
+//! alters a shipped schema.
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use schemars::generate::SchemaSettings;
+use schemars::{JsonSchema, Schema};
+
+use crate::files::FilesLum;
+use crate::loads::LoadsLum;
+
+/// The version of the structured representation.
+pub const SCHEMA_VERSION: u32 = 3;
+
+/// The file types with a published JSON Schema.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SchemaType {
+    LoadsLum,
+    FilesLum,
+}
+impl SchemaType {
+    /// All the file types with a published JSON Schema.
+    pub const ALL: [SchemaType; 2] = [SchemaType::LoadsLum, SchemaType::FilesLum];
+
+    /// Returns the name of the schema file, e.g. `loads-lum.json`.
+    #[must_use]
+    pub fn get_file_name(self) -> String {
+        format!("{self}.json")
+    }
+
+    /// Returns the `$id` of the schema.
+    #[must_use]
+    pub fn get_id(self) -> String {
+        format!(
+            "https://github.com/eagle941/arinc-explorer/schema/v{SCHEMA_VERSION}/{}",
+            self.get_file_name()
+        )
+    }
+
+    /// Generates the JSON Schema of the serialized form of the file type.
+    #[must_use]
+    pub fn generate(self) -> Schema {
+        match self {
+            SchemaType::LoadsLum => generate_for::<LoadsLum>(self),
+            SchemaType::FilesLum => generate_for::<FilesLum>(self),
+        }
+    }
+
+    /// Returns the JSON Schema shipped with the crate.
+    #[must_use]
+    pub fn get_published(self) -> &'static str {
+        match self {
+            SchemaType::LoadsLum => include_str!("../schema/v3/loads-lum.json"),
+            SchemaType::FilesLum => include_str!("../schema/v3/files-lum.json"),
+        }
+    }
+}
+impl Display for SchemaType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SchemaType::LoadsLum => write!(f, "loads-lum"),
+            SchemaType::FilesLum => write!(f, "files-lum"),
+        }
+    }
+}
+impl FromStr for SchemaType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SchemaType::ALL
+            .into_iter()
+            .find(|t| t.to_string() == s)
+            .ok_or(format!("{s:?} doesn't have a JSON Schema"))
+    }
+}
+
+fn generate_for<T: JsonSchema>(schema_type: SchemaType) -> Schema {
+    let mut schema = SchemaSettings::draft2020_12()
+        .for_serialize()
+        .into_generator()
+        .into_root_schema_for::<T>();
+    schema.insert("$id".to_string(), schema_type.get_id().into());
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    // Set `ARINC_UPDATE_SCHEMA=1` to regenerate the shipped schemas after an
+    // intentional change of the model.
+    #[test]
+    fn test_published_schemas() {
+        for schema_type in SchemaType::ALL {
+            let generated = serde_json::to_string_pretty(&schema_type.generate()).unwrap() + "\n";
+            if std::env::var_os("ARINC_UPDATE_SCHEMA").is_some() {
+                let path = PathBuf::from(format!(
+                    "schema/v{SCHEMA_VERSION}/{}",
+                    schema_type.get_file_name()
+                ));
+                std::fs::write(path, &generated).unwrap();
+                continue;
+            }
+            assert_eq!(
+                generated,
+                schema_type.get_published(),
+                "the {schema_type} schema changed, bump SCHEMA_VERSION if the change is \
+                 intentional"
+            );
+        }
+    }
+
+    #[test]
+    fn test_schema_annotations() {
+        let schema = SchemaType::LoadsLum.generate();
+        let properties = &schema.as_value()["properties"];
+        assert_eq!(properties["media_set_supplier"]["readOnly"], true);
+        assert!(properties["user_defined_data_decoded"].is_object());
+        assert!(schema.as_value()["$defs"]["Lsp"]["properties"]["load_supplier"].is_object());
+        let schema = SchemaType::FilesLum.generate();
+        assert!(schema.as_value()["properties"]["media_set_supplier"].is_object());
+    }
+
+    #[test]
+    fn test_schema_type() {
+        assert_eq!("files-lum".parse(), Ok(SchemaType::FilesLum));
+        assert!("batches-lum".parse::<SchemaType>().is_err());
+        assert_eq!(
+            SchemaType::LoadsLum.get_id(),
+            "https://github.com/eagle941/arinc-explorer/schema/v3/loads-lum.json"
+        );
+    }
+}
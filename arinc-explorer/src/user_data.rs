@@ -0,0 +1,287 @@
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter, Write};
+
+use crate::error::UserDataError;
+use crate::part_number::PartNumber;
+
+/// User defined data decoded by a [`UserDataDecoder`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum DecodedUserData {
+    /// The bytes as a hexadecimal string.
+    Hex(String),
+    Text(String),
+    Records(Vec<TlvRecord>),
+}
+impl Display for DecodedUserData {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            DecodedUserData::Hex(x) | DecodedUserData::Text(x) => write!(f, "{x}"),
+            DecodedUserData::Records(records) => {
+                for record in records {
+                    writeln!(f, "{record}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A tag-length-value record, see [`TlvDecoder`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TlvRecord {
+    pub tag: u16,
+    /// The value as a hexadecimal string.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_hex"))]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub value: Vec<u8>,
+}
+impl Display for TlvRecord {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if !self.value.is_empty() && self.value.iter().all(|b| is_printable(*b)) {
+            write!(
+                f,
+                "0x{:04x}: {:?}",
+                self.tag,
+                String::from_utf8_lossy(&self.value)
+            )
+        } else {
+            write!(f, "0x{:04x}: {}", self.tag, to_hex(&self.value))
+        }
+    }
+}
+
+/// A decoder of the user defined data stored in the ARINC 665 files.
+pub trait UserDataDecoder {
+    /// Returns the name used to select the decoder, e.g. `hex`.
+    fn get_name(&self) -> &str;
+
+    /// Decodes the user defined data bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the data doesn't follow the layout expected by the
+    /// decoder.
+    fn decode(&self, data: &[u8]) -> Result<DecodedUserData, UserDataError>;
+}
+
+/// Shows the data as a hexadecimal string.
+pub struct HexDecoder;
+impl UserDataDecoder for HexDecoder {
+    fn get_name(&self) -> &'static str {
+        "hex"
+    }
+
+    fn decode(&self, data: &[u8]) -> Result<DecodedUserData, UserDataError> {
+        Ok(DecodedUserData::Hex(to_hex(data)))
+    }
+}
+
+/// Decodes printable ASCII text, e.g. `KEY=VALUE` lines. The NUL bytes
+/// padding the data to a whole number of words are ignored.
+pub struct AsciiDecoder;
+impl UserDataDecoder for AsciiDecoder {
+    fn get_name(&self) -> &'static str {
+        "ascii"
+    }
+
+    fn decode(&self, data: &[u8]) -> Result<DecodedUserData, UserDataError> {
+        let end = data.iter().rposition(|b| *b != 0).map_or(0, |x| x + 1);
+        if let Some(offset) = data[..end]
+            .iter()
+            .position(|b| !(is_printable(*b) || b"\t\r\n".contains(b)))
+        {
+            return Err(UserDataError::InvalidAscii {
+                offset,
+                byte: data[offset],
+            });
+        }
+        Ok(DecodedUserData::Text(
+            String::from_utf8_lossy(&data[..end]).to_string(),
+        ))
+    }
+}
+
+/// Decodes a sequence of records made of a 16-bit tag, a 16-bit length in
+/// bytes and the value, padded with a zero byte to a whole number of words.
+/// Zero bytes after the last record are ignored.
+pub struct TlvDecoder;
+impl UserDataDecoder for TlvDecoder {
+    fn get_name(&self) -> &'static str {
+        "tlv"
+    }
+
+    fn decode(&self, data: &[u8]) -> Result<DecodedUserData, UserDataError> {
+        let mut records = Vec::new();
+        let mut offset = 0;
+        while data[offset..].iter().any(|b| *b != 0) {
+            let (Some(header), Some(rest)) = (data.get(offset..offset + 4), data.get(offset + 4..))
+            else {
+                return Err(UserDataError::TruncatedRecord { offset });
+            };
+            let tag = u16::from_be_bytes([header[0], header[1]]);
+            let length = usize::from(u16::from_be_bytes([header[2], header[3]]));
+            let Some(value) = rest.get(..length) else {
+                return Err(UserDataError::TruncatedRecord { offset });
+            };
+            records.push(TlvRecord {
+                tag,
+                value: value.to_vec(),
+            });
+            offset = (offset + 4 + length.div_ceil(2) * 2).min(data.len());
+        }
+        Ok(DecodedUserData::Records(records))
+    }
+}
+
+/// The user defined data decoders, selected by name or by media set PN
+/// prefix.
+///
+/// [`UserDataRegistry::default`] contains the built-in `hex`, `ascii` and
+/// `tlv` decoders. The `hex` decoder is used when no prefix matches.
+pub struct UserDataRegistry {
+    decoders: Vec<Box<dyn UserDataDecoder>>,
+    prefixes: Vec<(String, String)>,
+}
+impl UserDataRegistry {
+    /// Registers a decoder, replacing the one with the same name.
+    pub fn register(&mut self, decoder: Box<dyn UserDataDecoder>) {
+        self.decoders.retain(|d| d.get_name() != decoder.get_name());
+        self.decoders.push(decoder);
+    }
+
+    /// Selects the decoder `name` for the media sets whose PN starts with
+    /// `prefix`. The longest matching prefix wins.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if no decoder named `name` is registered.
+    pub fn set_prefix(&mut self, prefix: &str, name: &str) -> Result<(), UserDataError> {
+        if self.get(name).is_none() {
+            return Err(UserDataError::UnknownDecoder(name.to_string()));
+        }
+        self.prefixes.retain(|(p, _)| p != prefix);
+        self.prefixes.push((prefix.to_string(), name.to_string()));
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&dyn UserDataDecoder> {
+        self.decoders
+            .iter()
+            .find(|d| d.get_name() == name)
+            .map(AsRef::as_ref)
+    }
+
+    /// Returns the decoder selected for a media set, `hex` if no prefix
+    /// matches.
+    #[must_use]
+    pub fn select(&self, media_set_pn: &PartNumber) -> &dyn UserDataDecoder {
+        self.prefixes
+            .iter()
+            .filter(|(prefix, _)| media_set_pn.as_str().starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .and_then(|(_, name)| self.get(name))
+            .unwrap_or(&HexDecoder)
+    }
+
+    /// Returns the names of the registered decoders.
+    pub fn get_names(&self) -> impl Iterator<Item = &str> {
+        self.decoders.iter().map(|d| d.get_name())
+    }
+}
+impl Default for UserDataRegistry {
+    fn default() -> Self {
+        Self {
+            decoders: vec![
+                Box::new(HexDecoder),
+                Box::new(AsciiDecoder),
+                Box::new(TlvDecoder),
+            ],
+            prefixes: Vec::new(),
+        }
+    }
+}
+
+fn is_printable(byte: u8) -> bool {
+    byte.is_ascii_graphic() || byte == b' '
+}
+
+fn to_hex(data: &[u8]) -> String {
+    data.iter().fold(String::new(), |mut acc, b| {
+        let _ = write!(acc, "{b:02x}");
+        acc
+    })
+}
+
+#[cfg(feature = "serde")]
+#[allow(clippy::ptr_arg)]
+fn serialize_hex<S: serde::Serializer>(value: &Vec<u8>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&to_hex(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_data_decoders() {
+        let data = b"\x00\x01\x00\x03ABC\x00\x00\x02\x00\x01\xff\x00\x00\x00";
+        assert_eq!(
+            TlvDecoder.decode(data).unwrap(),
+            DecodedUserData::Records(vec![
+                TlvRecord {
+                    tag: 1,
+                    value: b"ABC".to_vec()
+                },
+                TlvRecord {
+                    tag: 2,
+                    value: vec![0xff]
+                },
+            ])
+        );
+        assert_eq!(
+            TlvDecoder.decode(b"\x00\x01\x00\x08AB"),
+            Err(UserDataError::TruncatedRecord { offset: 0 })
+        );
+        assert_eq!(
+            AsciiDecoder.decode(b"BUILD=42\0").unwrap(),
+            DecodedUserData::Text("BUILD=42".to_string())
+        );
+        assert_eq!(
+            AsciiDecoder.decode(b"A\xe9"),
+            Err(UserDataError::InvalidAscii {
+                offset: 1,
+                byte: 0xe9
+            })
+        );
+        assert_eq!(HexDecoder.decode(&[1, 0xab]).unwrap().to_string(), "01ab");
+    }
+
+    #[test]
+    fn test_user_data_registry() {
+        let mut registry = UserDataRegistry::default();
+        registry.set_prefix("ABC", "ascii").unwrap();
+        registry.set_prefix("ABC18", "tlv").unwrap();
+        assert!(registry.set_prefix("XYZ", "json").is_err());
+
+        let select = |pn: &str| registry.select(&PartNumber::new(pn)).get_name();
+        assert_eq!(select("ABC1813001"), "tlv");
+        assert_eq!(select("ABCDEFGH12"), "ascii");
+        assert_eq!(select("XYZ7313001"), "hex");
+        assert_eq!(
+            registry.get_names().collect::<Vec<_>>(),
+            vec!["hex", "ascii", "tlv"]
+        );
+    }
+}
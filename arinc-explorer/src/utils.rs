@@ -1,3 +1,9 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use binrw::{BinRead, BinResult};
+
 pub fn combine_words(msb: u16, lsb: u16) -> u32 {
     let msb: u32 = msb.into();
     let lsb: u32 = lsb.into();
@@ -11,8 +17,108 @@ pub fn vec16_to_string(input: &[u16], length: usize) -> String {
     String::from_utf8_lossy(slice).to_string()
 }
 
+pub fn split_words(value: u32) -> (u16, u16) {
+    let msb = (value >> 16) as u16;
+    let lsb = (value & 0xFFFF) as u16;
+    (msb, lsb)
+}
+
+// Returns the words storing `input`, the last one is padded with a NUL byte
+// when the length is odd.
+pub fn string_to_vec16(input: &str) -> Vec<u16> {
+    bytes_to_vec16(input.as_bytes())
+}
+
+// Returns the words storing `input`, the last one is padded with a zero byte
+// when the length is odd.
+pub fn bytes_to_vec16(input: &[u8]) -> Vec<u16> {
+    input
+        .chunks(2)
+        .map(|c| u16::from_be_bytes([c[0], c.get(1).copied().unwrap_or_default()]))
+        .collect()
+}
+
+// Returns the number of words between the end of an entry of `size` words and
+// the entry `pointer` words after its beginning. The last entry has a null
+// pointer, hence it never has any.
+pub fn expansion_words(pointer: u16, size: usize) -> usize {
+    if pointer == 0 {
+        0
+    } else {
+        usize::from(pointer).saturating_sub(size)
+    }
+}
+
+// Reads the words from the current position up to `end`, an offset in 16-bit
+// words from the beginning of the file. Nothing is read if the position is
+// already past `end`.
+#[binrw::parser(reader, endian)]
+pub fn read_words_until(end: u32) -> BinResult<Vec<u16>> {
+    let position = reader.stream_position()? / 2;
+    (position..u64::from(end))
+        .map(|_| u16::read_options(reader, endian, ()))
+        .collect()
+}
+
+// Reads the user defined data from `pointer` up to `end`, offsets in 16-bit
+// words from the beginning of the file. A null pointer means the file has no
+// user defined data, a pointer past `end` is an error.
+#[binrw::parser(reader, endian)]
+pub fn read_user_defined_data(pointer: u32, end: u32) -> BinResult<Option<Vec<u16>>> {
+    if pointer == 0 {
+        return Ok(None);
+    }
+    let Some(count) = end.checked_sub(pointer) else {
+        return Err(binrw::Error::AssertFail {
+            pos: reader.stream_position()?,
+            message: alloc::format!(
+                "pointer_to_user_defined_data {pointer} is past the end of the user defined data \
+                 {end}"
+            ),
+        });
+    };
+    (0..count)
+        .map(|_| u16::read_options(reader, endian, ()))
+        .collect::<BinResult<_>>()
+        .map(Some)
+}
+
+pub fn vec16_to_bytes(input: &[u16]) -> Vec<u8> {
+    input.iter().flat_map(|w| w.to_be_bytes()).collect()
+}
+
+pub fn vec16_to_hex(input: &[u16]) -> String {
+    input.iter().fold(String::new(), |mut acc, w| {
+        let _ = write!(acc, "{w:04x}");
+        acc
+    })
+}
+
+// Parses a string of hexadecimal digits, the last word is padded with zeros
+// when the number of bytes is odd.
+#[cfg(feature = "serde")]
+pub fn hex_to_vec16(input: &str) -> Option<Vec<u16>> {
+    if !input.is_ascii() || input.len() % 2 != 0 {
+        return None;
+    }
+    let bytes = (0..input.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&input[i..i + 2], 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    Some(
+        bytes
+            .chunks(2)
+            .map(|c| u16::from_be_bytes([c[0], c.get(1).copied().unwrap_or_default()]))
+            .collect(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
+    use std::io::Cursor;
+
+    use binrw::Endian;
+
     use super::*;
 
     #[test]
@@ -36,6 +142,33 @@ mod tests {
         let msb = 0xffff;
         let lsb = 0xeeee;
         assert_eq!(combine_words(msb, lsb), 0xffff_eeee);
+        assert_eq!(split_words(0xffff_eeee), (msb, lsb));
+    }
+
+    #[test]
+    fn test_string_to_vec16() {
+        assert_eq!(string_to_vec16("ABCD"), vec![0x4142, 0x4344]);
+        assert_eq!(string_to_vec16("ABC"), vec![0x4142, 0x4300]);
+        assert_eq!(string_to_vec16(""), Vec::<u16>::new());
+        assert_eq!(vec16_to_bytes(&[0x4142, 0x4300]), b"ABC\0");
+    }
+
+    #[test]
+    fn test_read_words_until() {
+        let mut reader = Cursor::new([0x00, 0x01, 0x00, 0x02, 0x00, 0x03]);
+        reader.set_position(2);
+        let words = read_words_until(&mut reader, Endian::Big, (3,)).unwrap();
+        assert_eq!(words, vec![0x0002, 0x0003]);
+        let words = read_words_until(&mut reader, Endian::Big, (1,)).unwrap();
+        assert_eq!(words, Vec::<u16>::new());
+        let words = read_user_defined_data(&mut reader, Endian::Big, (0, 3)).unwrap();
+        assert_eq!(words, None);
+        reader.set_position(2);
+        let words = read_user_defined_data(&mut reader, Endian::Big, (1, 3)).unwrap();
+        assert_eq!(words, Some(vec![0x0002, 0x0003]));
+        assert!(read_user_defined_data(&mut reader, Endian::Big, (4, 3)).is_err());
+        assert_eq!(expansion_words(0, 5), 0);
+        assert_eq!(expansion_words(7, 5), 2);
     }
 
     #[test]
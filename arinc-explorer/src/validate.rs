@@ -0,0 +1,472 @@
+//! The structural, CRC and cross-reference checks of a media.
+
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::arinc_file::{ArincFile, MediaSetFile};
+use crate::arinc_string::StringIssue;
+use crate::crc::crc16;
+use crate::files::File;
+use crate::media_set::MediaSet;
+use crate::part_number::PartNumber;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Severity {
+    /// Something worth knowing which doesn't affect the media, e.g. a file on
+    /// another media of the set.
+    Info,
+    /// The media can be loaded but it doesn't fully conform, e.g. a PN with
+    /// wrong check characters.
+    Warning,
+    /// The media is corrupted or inconsistent.
+    Error,
+}
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A problem found by [`validate`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Finding {
+    pub severity: Severity,
+    /// Where the problem is, e.g. `LOADS.LUM: loads[0].load_pn` or the
+    /// pathname of a file.
+    pub location: String,
+    pub message: String,
+}
+impl Display for Finding {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.severity, self.location, self.message)
+    }
+}
+
+#[derive(Default)]
+struct Findings(Vec<Finding>);
+impl Findings {
+    fn push(
+        &mut self,
+        severity: Severity,
+        location: impl Into<String>,
+        message: impl Into<String>,
+    ) {
+        self.0.push(Finding {
+            severity,
+            location: location.into(),
+            message: message.into(),
+        });
+    }
+}
+
+/// Runs every check on a media and returns the findings, in the order they
+/// are found.
+///
+/// The media can only be validated once [`MediaSet::open`] has parsed its
+/// `LOADS.LUM` and `FILES.LUM`.
+#[must_use]
+pub fn validate(media_set: &MediaSet) -> Vec<Finding> {
+    let mut findings = Findings::default();
+    check_file(
+        &mut findings,
+        media_set.get_root(),
+        "LOADS.LUM",
+        media_set.get_loads_lum(),
+    );
+    check_file(
+        &mut findings,
+        media_set.get_root(),
+        "FILES.LUM",
+        media_set.get_files_lum(),
+    );
+    check_cross_references(&mut findings, media_set);
+    check_files(&mut findings, media_set);
+    findings.0
+}
+
+// Structural and CRC checks of a LUM file.
+fn check_file<T>(findings: &mut Findings, root: &Path, name: &str, file: &T)
+where
+    T: MediaSetFile + Clone,
+    T::Error: Display,
+{
+    let format_version = file.get_format_version();
+    if !format_version.is_media_file() {
+        findings.push(
+            Severity::Error,
+            name,
+            format!("{format_version} is not a media file format version"),
+        );
+    }
+
+    let length = u64::from(file.get_file_length()) * 2;
+    if let Ok(metadata) = fs::metadata(root.join(name)) {
+        if metadata.len() != length {
+            findings.push(
+                Severity::Error,
+                name,
+                format!(
+                    "the file is {} bytes long, the header declares {length}",
+                    metadata.len()
+                ),
+            );
+        }
+    }
+
+    // The lengths, counts and pointers must match the content.
+    let mut expected = file.clone();
+    let maps = expected
+        .update_layout()
+        .and_then(|()| Ok((file.get_field_map()?, expected.get_field_map()?)));
+    match maps {
+        Ok((found, expected)) => {
+            for (found, expected) in found.iter().zip(&expected) {
+                if found.name == expected.name
+                    && found.name != "file_crc"
+                    && found.value != expected.value
+                {
+                    findings.push(
+                        Severity::Error,
+                        format!("{name}: {}", found.name),
+                        format!("found {}, expected {}", found.value, expected.value),
+                    );
+                }
+            }
+        }
+        Err(e) => findings.push(Severity::Error, name, e.to_string()),
+    }
+
+    match file.compute_file_crc() {
+        Ok(crc) if crc != file.get_file_crc() => findings.push(
+            Severity::Error,
+            format!("{name}: file_crc"),
+            format!("found 0x{:04x}, computed 0x{crc:04x}", file.get_file_crc()),
+        ),
+        Ok(_) => {}
+        Err(e) => findings.push(Severity::Error, name, e.to_string()),
+    }
+
+    if file.get_media_sequence_number() == 0
+        || file.get_media_sequence_number() > file.get_number_of_media_set_members()
+    {
+        findings.push(
+            Severity::Error,
+            format!("{name}: media_sequence_number"),
+            format!(
+                "media {} of {} doesn't exist",
+                file.get_media_sequence_number(),
+                file.get_number_of_media_set_members()
+            ),
+        );
+    }
+    check_part_number(
+        findings,
+        &format!("{name}: media_set_pn"),
+        &file.get_media_set_pn(),
+    );
+}
+
+fn check_part_number(findings: &mut Findings, location: &str, part_number: &PartNumber) {
+    if let Err(e) = part_number.validate() {
+        findings.push(Severity::Warning, location, format!("{part_number}: {e}"));
+    }
+}
+
+fn check_string_issues(findings: &mut Findings, name: &str, issues: Vec<StringIssue>) {
+    for issue in issues {
+        findings.push(
+            Severity::Warning,
+            format!("{name}: {}", issue.field),
+            issue.error.to_string(),
+        );
+    }
+}
+
+// Checks that LOADS.LUM and FILES.LUM describe the same media and that every
+// load header is listed.
+fn check_cross_references(findings: &mut Findings, media_set: &MediaSet) {
+    let loads_lum = media_set.get_loads_lum();
+    let files_lum = media_set.get_files_lum();
+    check_string_issues(findings, "LOADS.LUM", loads_lum.get_string_issues());
+    check_string_issues(findings, "FILES.LUM", files_lum.get_string_issues());
+
+    if loads_lum.get_media_set_pn() != files_lum.get_media_set_pn() {
+        findings.push(
+            Severity::Error,
+            "FILES.LUM: media_set_pn",
+            format!(
+                "{} doesn't match {} in LOADS.LUM",
+                files_lum.get_media_set_pn(),
+                loads_lum.get_media_set_pn()
+            ),
+        );
+    }
+    if (
+        loads_lum.get_media_sequence_number(),
+        loads_lum.get_number_of_media_set_members(),
+    ) != (
+        files_lum.get_media_sequence_number(),
+        files_lum.get_number_of_media_set_members(),
+    ) {
+        findings.push(
+            Severity::Error,
+            "FILES.LUM: media_sequence_number",
+            format!(
+                "media {} of {} doesn't match media {} of {} in LOADS.LUM",
+                files_lum.get_media_sequence_number(),
+                files_lum.get_number_of_media_set_members(),
+                loads_lum.get_media_sequence_number(),
+                loads_lum.get_number_of_media_set_members()
+            ),
+        );
+    }
+    if files_lum.get_format_version() != loads_lum.get_format_version() {
+        findings.push(
+            Severity::Warning,
+            "FILES.LUM",
+            format!(
+                "format version {} doesn't match {} in LOADS.LUM",
+                files_lum.get_format_version(),
+                loads_lum.get_format_version()
+            ),
+        );
+    }
+
+    let files = files_lum.get_media_set_files();
+    let mut load_pns = HashSet::new();
+    for (i, load) in loads_lum.get_loads().iter().enumerate() {
+        let location = format!("LOADS.LUM: loads[{i}]");
+        check_part_number(
+            findings,
+            &format!("{location}.load_pn"),
+            &load.get_load_pn(),
+        );
+        if !load_pns.insert(load.get_load_pn()) {
+            findings.push(
+                Severity::Warning,
+                format!("{location}.load_pn"),
+                format!("{} is listed more than once", load.get_load_pn()),
+            );
+        }
+        if !files
+            .iter()
+            .any(|file| file.get_file_name() == load.get_header_filename())
+        {
+            findings.push(
+                Severity::Error,
+                format!("{location}.header_filename"),
+                format!("{} is not listed in FILES.LUM", load.get_header_filename()),
+            );
+        }
+        if load.get_member_sequence_number() == 0
+            || load.get_member_sequence_number()
+                > u16::from(loads_lum.get_number_of_media_set_members())
+        {
+            findings.push(
+                Severity::Error,
+                format!("{location}.member_sequence_number"),
+                format!(
+                    "media {} is not part of the media set",
+                    load.get_member_sequence_number()
+                ),
+            );
+        }
+    }
+}
+
+fn get_pathname(file: &File) -> String {
+    format!("{}{}", file.get_file_pathname(), file.get_file_name())
+}
+
+// Checks the files listed in FILES.LUM and looks for the unlisted ones.
+fn check_files(findings: &mut Findings, media_set: &MediaSet) {
+    let files_lum = media_set.get_files_lum();
+    let mut paths = HashSet::new();
+    for (i, file) in files_lum.get_media_set_files().iter().enumerate() {
+        let location = format!("FILES.LUM: media_set_files[{i}]");
+        let path = match media_set.get_file_path(file) {
+            Ok(x) => x,
+            Err(e) => {
+                findings.push(Severity::Error, location, e.to_string());
+                continue;
+            }
+        };
+        if !paths.insert(path.clone()) {
+            findings.push(
+                Severity::Warning,
+                location,
+                format!("{} is listed more than once", get_pathname(file)),
+            );
+            continue;
+        }
+        if file.get_member_sequence_number() != u16::from(files_lum.get_media_sequence_number()) {
+            findings.push(
+                Severity::Info,
+                get_pathname(file),
+                format!("stored on media {}", file.get_member_sequence_number()),
+            );
+            continue;
+        }
+        let content = match fs::read(&path) {
+            Ok(x) => x,
+            Err(e) => {
+                findings.push(Severity::Error, get_pathname(file), e.to_string());
+                continue;
+            }
+        };
+        if crc16(&content) != file.get_file_crc() {
+            findings.push(
+                Severity::Error,
+                get_pathname(file),
+                format!(
+                    "the CRC doesn't match 0x{:04x} listed in FILES.LUM",
+                    file.get_file_crc()
+                ),
+            );
+        }
+        match file.has_valid_check_value(&content) {
+            Ok(None | Some(true)) => {}
+            Ok(Some(false)) => findings.push(
+                Severity::Error,
+                get_pathname(file),
+                "the check value doesn't match the one listed in FILES.LUM",
+            ),
+            Err(e) => findings.push(
+                Severity::Warning,
+                get_pathname(file),
+                format!("the check value can't be verified: {e}"),
+            ),
+        }
+    }
+
+    let root = media_set.get_root();
+    paths.insert(root.join("LOADS.LUM"));
+    paths.insert(root.join("FILES.LUM"));
+    let mut unlisted = Vec::new();
+    collect_unlisted(root, &paths, &mut unlisted);
+    for path in unlisted {
+        let relative = path.strip_prefix(root).unwrap_or(&path);
+        findings.push(
+            Severity::Info,
+            relative.display().to_string(),
+            "not listed in FILES.LUM",
+        );
+    }
+}
+
+fn collect_unlisted(dir: &Path, listed: &HashSet<PathBuf>, unlisted: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<_> = entries.filter_map(Result::ok).collect();
+    entries.sort_by_key(fs::DirEntry::file_name);
+    for entry in entries {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_unlisted(&path, listed, unlisted);
+        } else if !listed.contains(&path) {
+            unlisted.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::check_value::{CheckValueHasher, CheckValueType};
+    use crate::files::FilesLum;
+    use crate::format_version::FormatVersion;
+    use crate::header::MediaSetInfo;
+    use crate::loads::LoadsLum;
+
+    // Writes an ARINC 665-3 media whose files have a check value: A.BIN is
+    // intact, the check value of B.BIN is wrong and the one of C.BIN is an
+    // MD5, which can't be verified.
+    pub(crate) fn write_media_with_check_values(root: &Path) {
+        let media_set = MediaSetInfo::new(&PartNumber::new("ABC7313001"), 1, 1).unwrap();
+        let mut files = Vec::new();
+        for (name, check_value_type) in [
+            ("A.BIN", CheckValueType::Crc32),
+            ("B.BIN", CheckValueType::Crc32),
+            ("C.BIN", CheckValueType::Md5),
+        ] {
+            let content = name.as_bytes();
+            fs::write(root.join(name), content).unwrap();
+            let mut file = File::new(name, "\\", 1, crc16(content)).unwrap();
+            let mut hasher = CheckValueHasher::new(CheckValueType::Crc32).unwrap();
+            hasher.update(if name == "B.BIN" { b"other" } else { content });
+            file.set_check_value(check_value_type, &hasher.finish())
+                .unwrap();
+            files.push(file);
+        }
+        let loads_lum = LoadsLum::from_loads(
+            FormatVersion::Arinc665_3Media,
+            media_set.clone(),
+            Vec::new(),
+        )
+        .unwrap();
+        let files_lum =
+            FilesLum::from_files(FormatVersion::Arinc665_3Media, media_set, files).unwrap();
+        fs::write(root.join("LOADS.LUM"), loads_lum.to_bytes().unwrap()).unwrap();
+        fs::write(root.join("FILES.LUM"), files_lum.to_bytes().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_validate() {
+        let media_set = MediaSet::open(Path::new("../test-data")).unwrap();
+        let findings = validate(&media_set);
+        let has = |severity: Severity, location: &str| {
+            findings
+                .iter()
+                .any(|x| x.severity == severity && x.location == location)
+        };
+
+        // The test data is not a consistent media.
+        assert!(has(Severity::Error, "FILES.LUM: media_set_pn"));
+        assert!(has(Severity::Warning, "FILES.LUM: media_set_pn"));
+        assert!(has(Severity::Error, "FILES.LUM: file_crc"));
+        assert!(has(Severity::Error, "LOADS.LUM: loads[0].header_filename"));
+        assert!(has(Severity::Error, "\\ABC1813001\\ABC1813001.LUH"));
+        assert!(!has(Severity::Error, "LOADS.LUM: file_length"));
+        assert_eq!(
+            findings[0].to_string(),
+            "error: LOADS.LUM: file_crc: found 0x5246, computed 0xee35"
+        );
+    }
+
+    #[test]
+    fn test_validate_check_values() {
+        let dir = tempfile::tempdir().unwrap();
+        write_media_with_check_values(dir.path());
+        let media_set = MediaSet::open(dir.path()).unwrap();
+        let findings = validate(&media_set)
+            .into_iter()
+            .filter(|x| x.severity != Severity::Info)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            findings.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            [
+                "error: \\B.BIN: the check value doesn't match the one listed in FILES.LUM",
+                "warning: \\C.BIN: the check value can't be verified: MD5 check values are not \
+                 supported"
+            ]
+        );
+
+        let files = media_set.get_files_lum().get_media_set_files();
+        assert!(media_set.has_valid_file_crc(&files[0]).unwrap());
+        assert!(!media_set.has_valid_file_crc(&files[1]).unwrap());
+        assert!(media_set.has_valid_file_crc(&files[2]).unwrap());
+    }
+}
@@ -0,0 +1,261 @@
+#ifndef ARINC_EXPLORER_H
+#define ARINC_EXPLORER_H
+
+/* Generated by cbindgen, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The result of a function.
+typedef enum ArincStatus {
+  ARINC_STATUS_OK = 0,
+  // A required pointer is NULL.
+  ARINC_STATUS_NULL_POINTER,
+  // A string argument isn't valid UTF-8.
+  ARINC_STATUS_INVALID_STRING,
+  // An index is past the last entry.
+  ARINC_STATUS_OUT_OF_RANGE,
+  // The buffer is too small, the string has been truncated.
+  ARINC_STATUS_BUFFER_TOO_SMALL,
+  // A file can't be read.
+  ARINC_STATUS_IO,
+  // The content of a file is not valid.
+  ARINC_STATUS_PARSE,
+} ArincStatus;
+
+// A parsed `FILES.LUM` file.
+typedef struct ArincFilesLum ArincFilesLum;
+
+// A parsed `LOADS.LUM` file.
+typedef struct ArincLoadsLum ArincLoadsLum;
+
+// The media of a media set, see [`arinc_media_set_open`].
+typedef struct ArincMediaSet ArincMediaSet;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Returns the message of the last error of the calling thread, NULL if no
+// function has failed yet. The string is valid until the next failing call
+// on the same thread.
+const char *arinc_last_error_message(void);
+
+// Returns a static description of `status`.
+const char *arinc_status_string(enum ArincStatus status);
+
+// Parses a `LOADS.LUM` file. On success `*loads_lum` receives a handle to
+// release with [`arinc_loads_lum_free`].
+//
+// # Safety
+//
+// `path` must be NULL or a NUL terminated string, `loads_lum` must be NULL
+// or point to writable memory.
+enum ArincStatus arinc_loads_lum_open(const char *path, struct ArincLoadsLum **loads_lum);
+
+// Releases a handle returned by [`arinc_loads_lum_open`], NULL is ignored.
+//
+// # Safety
+//
+// `loads_lum` must be NULL or a handle returned by [`arinc_loads_lum_open`]
+// which hasn't been released yet.
+void arinc_loads_lum_free(struct ArincLoadsLum *loads_lum);
+
+// Copies the media set PN.
+//
+// # Safety
+//
+// `loads_lum` must be NULL or a valid handle, `buffer` must be NULL or point
+// to `size` writable bytes and `length` must be NULL or point to writable
+// memory.
+enum ArincStatus arinc_loads_lum_get_media_set_pn(const struct ArincLoadsLum *loads_lum,
+                                                  char *buffer,
+                                                  size_t size,
+                                                  size_t *length);
+
+// Returns the number of loads, 0 if `loads_lum` is NULL.
+//
+// # Safety
+//
+// `loads_lum` must be NULL or a valid handle.
+size_t arinc_loads_lum_get_load_count(const struct ArincLoadsLum *loads_lum);
+
+// Copies the PN of the load `load`.
+//
+// # Safety
+//
+// See [`arinc_loads_lum_get_media_set_pn`].
+enum ArincStatus arinc_loads_lum_get_load_pn(const struct ArincLoadsLum *loads_lum,
+                                             size_t load,
+                                             char *buffer,
+                                             size_t size,
+                                             size_t *length);
+
+// Copies the header filename of the load `load`.
+//
+// # Safety
+//
+// See [`arinc_loads_lum_get_media_set_pn`].
+enum ArincStatus arinc_loads_lum_get_load_header_filename(const struct ArincLoadsLum *loads_lum,
+                                                          size_t load,
+                                                          char *buffer,
+                                                          size_t size,
+                                                          size_t *length);
+
+// Returns the number of target HW IDs of the load `load`, 0 if `loads_lum`
+// is NULL or `load` is out of range.
+//
+// # Safety
+//
+// `loads_lum` must be NULL or a valid handle.
+size_t arinc_loads_lum_get_target_hw_id_count(const struct ArincLoadsLum *loads_lum, size_t load);
+
+// Copies the target HW ID `target_hw_id` of the load `load`.
+//
+// # Safety
+//
+// See [`arinc_loads_lum_get_media_set_pn`].
+enum ArincStatus arinc_loads_lum_get_target_hw_id(const struct ArincLoadsLum *loads_lum,
+                                                  size_t load,
+                                                  size_t target_hw_id,
+                                                  char *buffer,
+                                                  size_t size,
+                                                  size_t *length);
+
+// Checks the CRC stored at the end of the file against its content.
+//
+// # Safety
+//
+// `loads_lum` must be NULL or a valid handle, `valid` must be NULL or point
+// to writable memory.
+enum ArincStatus arinc_loads_lum_verify_crc(const struct ArincLoadsLum *loads_lum, bool *valid);
+
+// Parses a `FILES.LUM` file. On success `*files_lum` receives a handle to
+// release with [`arinc_files_lum_free`].
+//
+// # Safety
+//
+// `path` must be NULL or a NUL terminated string, `files_lum` must be NULL
+// or point to writable memory.
+enum ArincStatus arinc_files_lum_open(const char *path, struct ArincFilesLum **files_lum);
+
+// Releases a handle returned by [`arinc_files_lum_open`], NULL is ignored.
+//
+// # Safety
+//
+// `files_lum` must be NULL or a handle returned by [`arinc_files_lum_open`]
+// which hasn't been released yet.
+void arinc_files_lum_free(struct ArincFilesLum *files_lum);
+
+// Copies the media set PN.
+//
+// # Safety
+//
+// `files_lum` must be NULL or a valid handle, `buffer` must be NULL or point
+// to `size` writable bytes and `length` must be NULL or point to writable
+// memory.
+enum ArincStatus arinc_files_lum_get_media_set_pn(const struct ArincFilesLum *files_lum,
+                                                  char *buffer,
+                                                  size_t size,
+                                                  size_t *length);
+
+// Returns the number of files, 0 if `files_lum` is NULL.
+//
+// # Safety
+//
+// `files_lum` must be NULL or a valid handle.
+size_t arinc_files_lum_get_file_count(const struct ArincFilesLum *files_lum);
+
+// Copies the name of the file `file`.
+//
+// # Safety
+//
+// See [`arinc_files_lum_get_media_set_pn`].
+enum ArincStatus arinc_files_lum_get_file_name(const struct ArincFilesLum *files_lum,
+                                               size_t file,
+                                               char *buffer,
+                                               size_t size,
+                                               size_t *length);
+
+// Copies the pathname of the file `file`.
+//
+// # Safety
+//
+// See [`arinc_files_lum_get_media_set_pn`].
+enum ArincStatus arinc_files_lum_get_file_pathname(const struct ArincFilesLum *files_lum,
+                                                   size_t file,
+                                                   char *buffer,
+                                                   size_t size,
+                                                   size_t *length);
+
+// Returns the CRC listed for the file `file` in `*crc`.
+//
+// # Safety
+//
+// `files_lum` must be NULL or a valid handle, `crc` must be NULL or point to
+// writable memory.
+enum ArincStatus arinc_files_lum_get_file_crc(const struct ArincFilesLum *files_lum,
+                                              size_t file,
+                                              uint16_t *crc);
+
+// Checks the CRC stored at the end of the file against its content.
+//
+// # Safety
+//
+// `files_lum` must be NULL or a valid handle, `valid` must be NULL or point
+// to writable memory.
+enum ArincStatus arinc_files_lum_verify_crc(const struct ArincFilesLum *files_lum, bool *valid);
+
+// Opens the media whose root directory is `root`, which contains
+// `LOADS.LUM` and `FILES.LUM`. On success `*media_set` receives a handle to
+// release with [`arinc_media_set_free`].
+//
+// # Safety
+//
+// `root` must be NULL or a NUL terminated string, `media_set` must be NULL
+// or point to writable memory.
+enum ArincStatus arinc_media_set_open(const char *root, struct ArincMediaSet **media_set);
+
+// Releases a handle returned by [`arinc_media_set_open`], NULL is ignored.
+// The `LOADS.LUM` and `FILES.LUM` handles of the media are released too.
+//
+// # Safety
+//
+// `media_set` must be NULL or a handle returned by [`arinc_media_set_open`]
+// which hasn't been released yet.
+void arinc_media_set_free(struct ArincMediaSet *media_set);
+
+// Returns the `LOADS.LUM` of the media, NULL if `media_set` is NULL. The
+// handle is owned by the media and must not be released.
+//
+// # Safety
+//
+// `media_set` must be NULL or a valid handle.
+const struct ArincLoadsLum *arinc_media_set_get_loads_lum(const struct ArincMediaSet *media_set);
+
+// Returns the `FILES.LUM` of the media, NULL if `media_set` is NULL. The
+// handle is owned by the media and must not be released.
+//
+// # Safety
+//
+// `media_set` must be NULL or a valid handle.
+const struct ArincFilesLum *arinc_media_set_get_files_lum(const struct ArincMediaSet *media_set);
+
+// Reads the file `file` listed in `FILES.LUM` and checks its CRC.
+//
+// # Safety
+//
+// `media_set` must be NULL or a valid handle, `valid` must be NULL or point
+// to writable memory.
+enum ArincStatus arinc_media_set_verify_file_crc(const struct ArincMediaSet *media_set,
+                                                 size_t file,
+                                                 bool *valid);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ARINC_EXPLORER_H */
@@ -0,0 +1,621 @@
+//! C bindings of `arinc-explorer`.
+//!
+//! The files are exposed as opaque handles which are created by the `_open`
+//! functions and released by the matching `_free` function. The functions
+//! return an [`ArincStatus`], on failure [`arinc_last_error_message`] describes
+//! the error. Strings are copied NUL terminated into buffers supplied by the
+//! caller, the entries are selected by index.
+//!
+//! The header `include/arinc_explorer.h` is generated with `cbindgen`.
+
+#![warn(
+    clippy::all,
+    clippy::cargo,
+    clippy::pedantic,
+    // clippy::missing_docs_in_private_items,
+    clippy::unwrap_used
+)]
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::fmt::Display;
+use std::path::Path;
+use std::ptr;
+
+use arinc_explorer::arinc_file::{ArincFile, MediaSetFile};
+use arinc_explorer::files::FilesLum;
+use arinc_explorer::loads::LoadsLum;
+use arinc_explorer::media_set::MediaSet;
+
+/// The result of a function.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ArincStatus {
+    Ok = 0,
+    /// A required pointer is NULL.
+    NullPointer,
+    /// A string argument isn't valid UTF-8.
+    InvalidString,
+    /// An index is past the last entry.
+    OutOfRange,
+    /// The buffer is too small, the string has been truncated.
+    BufferTooSmall,
+    /// A file can't be read.
+    Io,
+    /// The content of a file is not valid.
+    Parse,
+}
+
+/// A parsed `LOADS.LUM` file.
+pub struct ArincLoadsLum(LoadsLum);
+
+/// A parsed `FILES.LUM` file.
+pub struct ArincFilesLum(FilesLum);
+
+/// The media of a media set, see [`arinc_media_set_open`].
+pub struct ArincMediaSet {
+    media_set: MediaSet,
+    loads_lum: ArincLoadsLum,
+    files_lum: ArincFilesLum,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn fail(status: ArincStatus, error: impl Display) -> ArincStatus {
+    // Interior NUL bytes would truncate the message, they are dropped.
+    let message = error.to_string().replace('\0', "");
+    let message = CString::new(message).unwrap_or_default();
+    LAST_ERROR.with(|x| *x.borrow_mut() = Some(message));
+    status
+}
+
+// Converts a handle argument, NULL pointers are reported as errors.
+unsafe fn get<'a, T>(ptr: *const T, name: &str) -> Result<&'a T, ArincStatus> {
+    ptr.as_ref()
+        .ok_or_else(|| fail(ArincStatus::NullPointer, format!("{name} is NULL")))
+}
+
+unsafe fn get_str<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, ArincStatus> {
+    if ptr.is_null() {
+        return Err(fail(ArincStatus::NullPointer, format!("{name} is NULL")));
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|e| fail(ArincStatus::InvalidString, format!("{name}: {e}")))
+}
+
+fn get_entry<'a, T>(entries: &'a [T], index: usize, name: &str) -> Result<&'a T, ArincStatus> {
+    entries.get(index).ok_or_else(|| {
+        fail(
+            ArincStatus::OutOfRange,
+            format!(
+                "{name} {index} is out of range, there are {}",
+                entries.len()
+            ),
+        )
+    })
+}
+
+// Copies `value` and a NUL terminator to `buffer`, truncating it to `size`
+// bytes. `length`, if not NULL, receives the length of `value` without the
+// terminator.
+unsafe fn copy_string(
+    value: &str,
+    buffer: *mut c_char,
+    size: usize,
+    length: *mut usize,
+) -> ArincStatus {
+    if let Some(length) = length.as_mut() {
+        *length = value.len();
+    }
+    if buffer.is_null() {
+        return if size == 0 {
+            ArincStatus::BufferTooSmall
+        } else {
+            fail(ArincStatus::NullPointer, "buffer is NULL")
+        };
+    }
+    if size == 0 {
+        return ArincStatus::BufferTooSmall;
+    }
+    let count = value.len().min(size - 1);
+    ptr::copy_nonoverlapping(value.as_ptr().cast::<c_char>(), buffer, count);
+    *buffer.add(count) = 0;
+    if count < value.len() {
+        ArincStatus::BufferTooSmall
+    } else {
+        ArincStatus::Ok
+    }
+}
+
+unsafe fn set<T>(out: *mut T, value: T, name: &str) -> ArincStatus {
+    match out.as_mut() {
+        Some(out) => {
+            *out = value;
+            ArincStatus::Ok
+        }
+        None => fail(ArincStatus::NullPointer, format!("{name} is NULL")),
+    }
+}
+
+macro_rules! try_status {
+    ($e:expr) => {
+        match $e {
+            Ok(x) => x,
+            Err(status) => return status,
+        }
+    };
+}
+
+/// Returns the message of the last error of the calling thread, NULL if no
+/// function has failed yet. The string is valid until the next failing call
+/// on the same thread.
+#[no_mangle]
+pub extern "C" fn arinc_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|x| x.borrow().as_ref().map_or(ptr::null(), |x| x.as_ptr()))
+}
+
+/// Returns a static description of `status`.
+#[no_mangle]
+pub extern "C" fn arinc_status_string(status: ArincStatus) -> *const c_char {
+    let message: &[u8] = match status {
+        ArincStatus::Ok => b"no error\0",
+        ArincStatus::NullPointer => b"a required pointer is NULL\0",
+        ArincStatus::InvalidString => b"a string is not valid UTF-8\0",
+        ArincStatus::OutOfRange => b"an index is out of range\0",
+        ArincStatus::BufferTooSmall => b"the buffer is too small\0",
+        ArincStatus::Io => b"a file can't be read\0",
+        ArincStatus::Parse => b"the content of a file is not valid\0",
+    };
+    message.as_ptr().cast()
+}
+
+/// Parses a `LOADS.LUM` file. On success `*loads_lum` receives a handle to
+/// release with [`arinc_loads_lum_free`].
+///
+/// # Safety
+///
+/// `path` must be NULL or a NUL terminated string, `loads_lum` must be NULL
+/// or point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn arinc_loads_lum_open(
+    path: *const c_char,
+    loads_lum: *mut *mut ArincLoadsLum,
+) -> ArincStatus {
+    let path = try_status!(get_str(path, "path"));
+    if loads_lum.is_null() {
+        return fail(ArincStatus::NullPointer, "loads_lum is NULL");
+    }
+    match LoadsLum::new(Path::new(path)) {
+        Ok(x) => set(loads_lum, Box::into_raw(Box::new(ArincLoadsLum(x))), ""),
+        Err(e @ arinc_explorer::error::LoadsLumError::FileIO(_)) => fail(ArincStatus::Io, e),
+        Err(e) => fail(ArincStatus::Parse, e),
+    }
+}
+
+/// Releases a handle returned by [`arinc_loads_lum_open`], NULL is ignored.
+///
+/// # Safety
+///
+/// `loads_lum` must be NULL or a handle returned by [`arinc_loads_lum_open`]
+/// which hasn't been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn arinc_loads_lum_free(loads_lum: *mut ArincLoadsLum) {
+    if !loads_lum.is_null() {
+        drop(Box::from_raw(loads_lum));
+    }
+}
+
+/// Copies the media set PN.
+///
+/// # Safety
+///
+/// `loads_lum` must be NULL or a valid handle, `buffer` must be NULL or point
+/// to `size` writable bytes and `length` must be NULL or point to writable
+/// memory.
+#[no_mangle]
+pub unsafe extern "C" fn arinc_loads_lum_get_media_set_pn(
+    loads_lum: *const ArincLoadsLum,
+    buffer: *mut c_char,
+    size: usize,
+    length: *mut usize,
+) -> ArincStatus {
+    let loads_lum = try_status!(get(loads_lum, "loads_lum"));
+    copy_string(
+        loads_lum.0.get_media_set_pn().as_str(),
+        buffer,
+        size,
+        length,
+    )
+}
+
+/// Returns the number of loads, 0 if `loads_lum` is NULL.
+///
+/// # Safety
+///
+/// `loads_lum` must be NULL or a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn arinc_loads_lum_get_load_count(loads_lum: *const ArincLoadsLum) -> usize {
+    loads_lum.as_ref().map_or(0, |x| x.0.get_loads().len())
+}
+
+/// Copies the PN of the load `load`.
+///
+/// # Safety
+///
+/// See [`arinc_loads_lum_get_media_set_pn`].
+#[no_mangle]
+pub unsafe extern "C" fn arinc_loads_lum_get_load_pn(
+    loads_lum: *const ArincLoadsLum,
+    load: usize,
+    buffer: *mut c_char,
+    size: usize,
+    length: *mut usize,
+) -> ArincStatus {
+    let loads_lum = try_status!(get(loads_lum, "loads_lum"));
+    let load = try_status!(get_entry(loads_lum.0.get_loads(), load, "load"));
+    copy_string(load.get_load_pn().as_str(), buffer, size, length)
+}
+
+/// Copies the header filename of the load `load`.
+///
+/// # Safety
+///
+/// See [`arinc_loads_lum_get_media_set_pn`].
+#[no_mangle]
+pub unsafe extern "C" fn arinc_loads_lum_get_load_header_filename(
+    loads_lum: *const ArincLoadsLum,
+    load: usize,
+    buffer: *mut c_char,
+    size: usize,
+    length: *mut usize,
+) -> ArincStatus {
+    let loads_lum = try_status!(get(loads_lum, "loads_lum"));
+    let load = try_status!(get_entry(loads_lum.0.get_loads(), load, "load"));
+    copy_string(&load.get_header_filename(), buffer, size, length)
+}
+
+/// Returns the number of target HW IDs of the load `load`, 0 if `loads_lum`
+/// is NULL or `load` is out of range.
+///
+/// # Safety
+///
+/// `loads_lum` must be NULL or a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn arinc_loads_lum_get_target_hw_id_count(
+    loads_lum: *const ArincLoadsLum,
+    load: usize,
+) -> usize {
+    loads_lum
+        .as_ref()
+        .and_then(|x| x.0.get_loads().get(load))
+        .map_or(0, |x| x.get_target_hw_ids().len())
+}
+
+/// Copies the target HW ID `target_hw_id` of the load `load`.
+///
+/// # Safety
+///
+/// See [`arinc_loads_lum_get_media_set_pn`].
+#[no_mangle]
+pub unsafe extern "C" fn arinc_loads_lum_get_target_hw_id(
+    loads_lum: *const ArincLoadsLum,
+    load: usize,
+    target_hw_id: usize,
+    buffer: *mut c_char,
+    size: usize,
+    length: *mut usize,
+) -> ArincStatus {
+    let loads_lum = try_status!(get(loads_lum, "loads_lum"));
+    let load = try_status!(get_entry(loads_lum.0.get_loads(), load, "load"));
+    let target_hw_id = try_status!(get_entry(
+        load.get_target_hw_ids(),
+        target_hw_id,
+        "target_hw_id"
+    ));
+    copy_string(&target_hw_id.get_target_hw_id(), buffer, size, length)
+}
+
+/// Checks the CRC stored at the end of the file against its content.
+///
+/// # Safety
+///
+/// `loads_lum` must be NULL or a valid handle, `valid` must be NULL or point
+/// to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn arinc_loads_lum_verify_crc(
+    loads_lum: *const ArincLoadsLum,
+    valid: *mut bool,
+) -> ArincStatus {
+    let loads_lum = try_status!(get(loads_lum, "loads_lum"));
+    match loads_lum.0.has_valid_file_crc() {
+        Ok(x) => set(valid, x, "valid"),
+        Err(e) => fail(ArincStatus::Parse, e),
+    }
+}
+
+/// Parses a `FILES.LUM` file. On success `*files_lum` receives a handle to
+/// release with [`arinc_files_lum_free`].
+///
+/// # Safety
+///
+/// `path` must be NULL or a NUL terminated string, `files_lum` must be NULL
+/// or point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn arinc_files_lum_open(
+    path: *const c_char,
+    files_lum: *mut *mut ArincFilesLum,
+) -> ArincStatus {
+    let path = try_status!(get_str(path, "path"));
+    if files_lum.is_null() {
+        return fail(ArincStatus::NullPointer, "files_lum is NULL");
+    }
+    match FilesLum::new(Path::new(path)) {
+        Ok(x) => set(files_lum, Box::into_raw(Box::new(ArincFilesLum(x))), ""),
+        Err(e @ arinc_explorer::error::FilesLumError::FileIO(_)) => fail(ArincStatus::Io, e),
+        Err(e) => fail(ArincStatus::Parse, e),
+    }
+}
+
+/// Releases a handle returned by [`arinc_files_lum_open`], NULL is ignored.
+///
+/// # Safety
+///
+/// `files_lum` must be NULL or a handle returned by [`arinc_files_lum_open`]
+/// which hasn't been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn arinc_files_lum_free(files_lum: *mut ArincFilesLum) {
+    if !files_lum.is_null() {
+        drop(Box::from_raw(files_lum));
+    }
+}
+
+/// Copies the media set PN.
+///
+/// # Safety
+///
+/// `files_lum` must be NULL or a valid handle, `buffer` must be NULL or point
+/// to `size` writable bytes and `length` must be NULL or point to writable
+/// memory.
+#[no_mangle]
+pub unsafe extern "C" fn arinc_files_lum_get_media_set_pn(
+    files_lum: *const ArincFilesLum,
+    buffer: *mut c_char,
+    size: usize,
+    length: *mut usize,
+) -> ArincStatus {
+    let files_lum = try_status!(get(files_lum, "files_lum"));
+    copy_string(
+        files_lum.0.get_media_set_pn().as_str(),
+        buffer,
+        size,
+        length,
+    )
+}
+
+/// Returns the number of files, 0 if `files_lum` is NULL.
+///
+/// # Safety
+///
+/// `files_lum` must be NULL or a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn arinc_files_lum_get_file_count(files_lum: *const ArincFilesLum) -> usize {
+    files_lum
+        .as_ref()
+        .map_or(0, |x| x.0.get_media_set_files().len())
+}
+
+/// Copies the name of the file `file`.
+///
+/// # Safety
+///
+/// See [`arinc_files_lum_get_media_set_pn`].
+#[no_mangle]
+pub unsafe extern "C" fn arinc_files_lum_get_file_name(
+    files_lum: *const ArincFilesLum,
+    file: usize,
+    buffer: *mut c_char,
+    size: usize,
+    length: *mut usize,
+) -> ArincStatus {
+    let files_lum = try_status!(get(files_lum, "files_lum"));
+    let file = try_status!(get_entry(files_lum.0.get_media_set_files(), file, "file"));
+    copy_string(&file.get_file_name(), buffer, size, length)
+}
+
+/// Copies the pathname of the file `file`.
+///
+/// # Safety
+///
+/// See [`arinc_files_lum_get_media_set_pn`].
+#[no_mangle]
+pub unsafe extern "C" fn arinc_files_lum_get_file_pathname(
+    files_lum: *const ArincFilesLum,
+    file: usize,
+    buffer: *mut c_char,
+    size: usize,
+    length: *mut usize,
+) -> ArincStatus {
+    let files_lum = try_status!(get(files_lum, "files_lum"));
+    let file = try_status!(get_entry(files_lum.0.get_media_set_files(), file, "file"));
+    copy_string(&file.get_file_pathname(), buffer, size, length)
+}
+
+/// Returns the CRC listed for the file `file` in `*crc`.
+///
+/// # Safety
+///
+/// `files_lum` must be NULL or a valid handle, `crc` must be NULL or point to
+/// writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn arinc_files_lum_get_file_crc(
+    files_lum: *const ArincFilesLum,
+    file: usize,
+    crc: *mut u16,
+) -> ArincStatus {
+    let files_lum = try_status!(get(files_lum, "files_lum"));
+    let file = try_status!(get_entry(files_lum.0.get_media_set_files(), file, "file"));
+    set(crc, file.get_file_crc(), "crc")
+}
+
+/// Checks the CRC stored at the end of the file against its content.
+///
+/// # Safety
+///
+/// `files_lum` must be NULL or a valid handle, `valid` must be NULL or point
+/// to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn arinc_files_lum_verify_crc(
+    files_lum: *const ArincFilesLum,
+    valid: *mut bool,
+) -> ArincStatus {
+    let files_lum = try_status!(get(files_lum, "files_lum"));
+    match files_lum.0.has_valid_file_crc() {
+        Ok(x) => set(valid, x, "valid"),
+        Err(e) => fail(ArincStatus::Parse, e),
+    }
+}
+
+/// Opens the media whose root directory is `root`, which contains
+/// `LOADS.LUM` and `FILES.LUM`. On success `*media_set` receives a handle to
+/// release with [`arinc_media_set_free`].
+///
+/// # Safety
+///
+/// `root` must be NULL or a NUL terminated string, `media_set` must be NULL
+/// or point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn arinc_media_set_open(
+    root: *const c_char,
+    media_set: *mut *mut ArincMediaSet,
+) -> ArincStatus {
+    let root = try_status!(get_str(root, "root"));
+    if media_set.is_null() {
+        return fail(ArincStatus::NullPointer, "media_set is NULL");
+    }
+    match MediaSet::open(Path::new(root)) {
+        Ok(x) => {
+            let handle = ArincMediaSet {
+                loads_lum: ArincLoadsLum(x.get_loads_lum().clone()),
+                files_lum: ArincFilesLum(x.get_files_lum().clone()),
+                media_set: x,
+            };
+            set(media_set, Box::into_raw(Box::new(handle)), "")
+        }
+        Err(
+            e @ (arinc_explorer::error::MediaSetError::FileIO(_)
+            | arinc_explorer::error::MediaSetError::LoadsLum(
+                arinc_explorer::error::LoadsLumError::FileIO(_),
+            )
+            | arinc_explorer::error::MediaSetError::FilesLum(
+                arinc_explorer::error::FilesLumError::FileIO(_),
+            )),
+        ) => fail(ArincStatus::Io, e),
+        Err(e) => fail(ArincStatus::Parse, e),
+    }
+}
+
+/// Releases a handle returned by [`arinc_media_set_open`], NULL is ignored.
+/// The `LOADS.LUM` and `FILES.LUM` handles of the media are released too.
+///
+/// # Safety
+///
+/// `media_set` must be NULL or a handle returned by [`arinc_media_set_open`]
+/// which hasn't been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn arinc_media_set_free(media_set: *mut ArincMediaSet) {
+    if !media_set.is_null() {
+        drop(Box::from_raw(media_set));
+    }
+}
+
+/// Returns the `LOADS.LUM` of the media, NULL if `media_set` is NULL. The
+/// handle is owned by the media and must not be released.
+///
+/// # Safety
+///
+/// `media_set` must be NULL or a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn arinc_media_set_get_loads_lum(
+    media_set: *const ArincMediaSet,
+) -> *const ArincLoadsLum {
+    media_set.as_ref().map_or(ptr::null(), |x| &x.loads_lum)
+}
+
+/// Returns the `FILES.LUM` of the media, NULL if `media_set` is NULL. The
+/// handle is owned by the media and must not be released.
+///
+/// # Safety
+///
+/// `media_set` must be NULL or a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn arinc_media_set_get_files_lum(
+    media_set: *const ArincMediaSet,
+) -> *const ArincFilesLum {
+    media_set.as_ref().map_or(ptr::null(), |x| &x.files_lum)
+}
+
+/// Reads the file `file` listed in `FILES.LUM` and checks its CRC.
+///
+/// # Safety
+///
+/// `media_set` must be NULL or a valid handle, `valid` must be NULL or point
+/// to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn arinc_media_set_verify_file_crc(
+    media_set: *const ArincMediaSet,
+    file: usize,
+    valid: *mut bool,
+) -> ArincStatus {
+    let media_set = try_status!(get(media_set, "media_set"));
+    let files = media_set.files_lum.0.get_media_set_files();
+    let file = try_status!(get_entry(files, file, "file"));
+    match media_set.media_set.has_valid_file_crc(file) {
+        Ok(x) => set(valid, x, "valid"),
+        Err(e) => fail(ArincStatus::Io, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loads_lum() {
+        unsafe {
+            let path = CString::new("../test-data/LOADS.LUM").unwrap();
+            let mut loads_lum = ptr::null_mut();
+            assert_eq!(
+                arinc_loads_lum_open(path.as_ptr(), &mut loads_lum),
+                ArincStatus::Ok
+            );
+            assert_eq!(arinc_loads_lum_get_load_count(loads_lum), 1);
+
+            let mut buffer = [0; 4];
+            let mut length = 0;
+            assert_eq!(
+                arinc_loads_lum_get_load_pn(loads_lum, 0, buffer.as_mut_ptr(), 4, &mut length),
+                ArincStatus::BufferTooSmall
+            );
+            assert_eq!(length, 10);
+            assert_eq!(CStr::from_ptr(buffer.as_ptr()).to_str(), Ok("ABC"));
+            assert_eq!(
+                arinc_loads_lum_get_load_pn(loads_lum, 1, buffer.as_mut_ptr(), 4, &mut length),
+                ArincStatus::OutOfRange
+            );
+            assert_eq!(
+                CStr::from_ptr(arinc_last_error_message()).to_str(),
+                Ok("load 1 is out of range, there are 1")
+            );
+            arinc_loads_lum_free(loads_lum);
+
+            let path = CString::new("missing/LOADS.LUM").unwrap();
+            assert_eq!(
+                arinc_loads_lum_open(path.as_ptr(), &mut loads_lum),
+                ArincStatus::Io
+            );
+        }
+    }
+}
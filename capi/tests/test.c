@@ -0,0 +1,86 @@
+/* Exercises the C API with the test data, the media root is the first
+ * argument. Returns a non-zero status on the first failed check. */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "arinc_explorer.h"
+
+#define CHECK(condition)                                                     \
+  do {                                                                       \
+    if (!(condition)) {                                                      \
+      const char *message = arinc_last_error_message();                      \
+      fprintf(stderr, "%s:%d: check failed: %s (%s)\n", __FILE__, __LINE__, \
+              #condition, message ? message : "no error");                   \
+      return 1;                                                              \
+    }                                                                        \
+  } while (0)
+
+int main(int argc, char **argv) {
+  char path[4096];
+  char buffer[256];
+  size_t length = 0;
+  bool valid = true;
+  uint16_t crc = 0;
+  ArincMediaSet *media_set = NULL;
+  ArincLoadsLum *loads_lum = NULL;
+
+  CHECK(argc == 2);
+
+  /* Errors */
+  CHECK(arinc_last_error_message() == NULL);
+  CHECK(arinc_loads_lum_open("missing/LOADS.LUM", &loads_lum) ==
+        ARINC_STATUS_IO);
+  CHECK(arinc_last_error_message() != NULL);
+  CHECK(arinc_loads_lum_open(NULL, &loads_lum) == ARINC_STATUS_NULL_POINTER);
+  CHECK(strcmp(arinc_status_string(ARINC_STATUS_OUT_OF_RANGE),
+               "an index is out of range") == 0);
+
+  /* LOADS.LUM */
+  snprintf(path, sizeof(path), "%s/LOADS.LUM", argv[1]);
+  CHECK(arinc_loads_lum_open(path, &loads_lum) == ARINC_STATUS_OK);
+  CHECK(arinc_loads_lum_get_media_set_pn(loads_lum, buffer, sizeof(buffer),
+                                         &length) == ARINC_STATUS_OK);
+  CHECK(strcmp(buffer, "ABCDEFGH12") == 0 && length == 10);
+  CHECK(arinc_loads_lum_get_load_count(loads_lum) == 1);
+  CHECK(arinc_loads_lum_get_load_pn(loads_lum, 0, buffer, 4, &length) ==
+        ARINC_STATUS_BUFFER_TOO_SMALL);
+  CHECK(strcmp(buffer, "ABC") == 0 && length == 10);
+  CHECK(arinc_loads_lum_get_load_header_filename(
+            loads_lum, 0, buffer, sizeof(buffer), NULL) == ARINC_STATUS_OK);
+  CHECK(strcmp(buffer, "ABCDEFGH12.LUH") == 0);
+  CHECK(arinc_loads_lum_get_target_hw_id_count(loads_lum, 0) == 1);
+  CHECK(arinc_loads_lum_get_target_hw_id(loads_lum, 0, 0, buffer,
+                                         sizeof(buffer),
+                                         NULL) == ARINC_STATUS_OK);
+  CHECK(strcmp(buffer, "TYUIOP") == 0);
+  CHECK(arinc_loads_lum_get_target_hw_id(loads_lum, 0, 1, buffer,
+                                         sizeof(buffer),
+                                         NULL) == ARINC_STATUS_OUT_OF_RANGE);
+  CHECK(arinc_loads_lum_verify_crc(loads_lum, &valid) == ARINC_STATUS_OK);
+  arinc_loads_lum_free(loads_lum);
+
+  /* Media set */
+  CHECK(arinc_media_set_open(argv[1], &media_set) == ARINC_STATUS_OK);
+  const ArincFilesLum *files_lum = arinc_media_set_get_files_lum(media_set);
+  CHECK(arinc_loads_lum_get_load_count(
+            arinc_media_set_get_loads_lum(media_set)) == 1);
+  CHECK(arinc_files_lum_get_file_count(files_lum) == 14);
+  CHECK(arinc_files_lum_get_file_name(files_lum, 0, buffer, sizeof(buffer),
+                                      NULL) == ARINC_STATUS_OK);
+  CHECK(strcmp(buffer, "ABC1813001.LUH") == 0);
+  CHECK(arinc_files_lum_get_file_pathname(files_lum, 0, buffer,
+                                          sizeof(buffer),
+                                          NULL) == ARINC_STATUS_OK);
+  CHECK(strcmp(buffer, "\\ABC1813001\\") == 0);
+  CHECK(arinc_files_lum_get_file_crc(files_lum, 0, &crc) == ARINC_STATUS_OK);
+  CHECK(crc == 0xd23e);
+  CHECK(arinc_files_lum_verify_crc(files_lum, &valid) == ARINC_STATUS_OK);
+  /* The loads aren't part of the test data. */
+  CHECK(arinc_media_set_verify_file_crc(media_set, 0, &valid) ==
+        ARINC_STATUS_IO);
+  arinc_media_set_free(media_set);
+
+  printf("ok\n");
+  return 0;
+}
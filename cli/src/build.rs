@@ -0,0 +1,371 @@
+//! Writes a media set described by a TOML or YAML manifest.
+//!
+//! ```toml
+//! media_set_pn = "ABC1813001"
+//! format_version = "ARINC665-2"
+//! members = 1
+//!
+//! [[loads]]
+//! load_pn = "ABC1813001"
+//! header = "build/ABC1813001.LUH"
+//! files = ["build/ABC1813001001.LUP", "build/ABC1813001_CONF_XYZ01.XML"]
+//! target_hw_ids = ["TYUIOP"]
+//! directory = "\\ABC1813001\\"
+//! ```
+//!
+//! The sources are relative to the manifest. The header and the files of a
+//! load are stored on its `member` (1 by default) in `directory` (the root by
+//! default), the extra `[[files]]` entries have their own `member` and
+//! `directory`. The `format_version` is one of the media file versions,
+//! `ARINC665-1`, `ARINC665-2` or `ARINC665-3 media`. With ARINC 665-3 every
+//! file listed in `FILES.LUM` gets a CRC-32 check value.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use arinc_explorer::arinc_file::ArincFile;
+use arinc_explorer::check_value::{CheckValueHasher, CheckValueType};
+use arinc_explorer::crc::crc16;
+use arinc_explorer::files::{File, FilesLum};
+use arinc_explorer::format_version::FormatVersion;
+use arinc_explorer::header::MediaSetInfo;
+use arinc_explorer::load_header::LoadHeaderFiles;
+use arinc_explorer::loads::{LoadsLum, Lsp, TargetHW};
+use arinc_explorer::media_set::get_relative_path;
+use arinc_explorer::part_number::PartNumber;
+use serde::{Deserialize, Serialize};
+
+fn default_member() -> u8 {
+    1
+}
+
+fn default_directory() -> String {
+    "\\".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    media_set_pn: String,
+    /// Parsed with [`FormatVersion::from_str`], e.g. `ARINC665-2`.
+    format_version: String,
+    #[serde(default = "default_member")]
+    members: u8,
+    #[serde(default)]
+    loads: Vec<ManifestLoad>,
+    /// Files stored on the media set without being part of a load.
+    #[serde(default)]
+    files: Vec<ManifestFile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManifestLoad {
+    load_pn: String,
+    header: PathBuf,
+    /// The data and support files listed in the header.
+    #[serde(default)]
+    files: Vec<PathBuf>,
+    #[serde(default)]
+    target_hw_ids: Vec<String>,
+    #[serde(default = "default_member")]
+    member: u8,
+    #[serde(default = "default_directory")]
+    directory: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManifestFile {
+    source: PathBuf,
+    #[serde(default = "default_member")]
+    member: u8,
+    #[serde(default = "default_directory")]
+    directory: String,
+}
+
+/// A file written by [`build_media_set`].
+#[derive(Debug, Serialize)]
+pub struct BuiltFile {
+    /// The path relative to the output directory.
+    path: PathBuf,
+    member_sequence_number: u8,
+    file_crc: String,
+}
+impl Display for BuiltFile {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} on media {}, CRC {}",
+            self.path.display(),
+            self.member_sequence_number,
+            self.file_crc
+        )
+    }
+}
+
+fn read_manifest(path: &Path) -> anyhow::Result<Manifest> {
+    let content = fs::read_to_string(path)?;
+    match path.extension().and_then(|x| x.to_str()) {
+        Some("toml") => Ok(toml::from_str(&content)?),
+        Some("yaml" | "yml") => Ok(serde_yaml::from_str(&content)?),
+        _ => Err(anyhow!("{path:?} is not a .toml or .yaml manifest.")),
+    }
+}
+
+// The content of a file of the media set and where it's stored.
+struct Source {
+    file: File,
+    content: Vec<u8>,
+}
+impl Source {
+    fn read(source: &Path, directory: &str, member: u8) -> anyhow::Result<Self> {
+        let name = source
+            .file_name()
+            .and_then(|x| x.to_str())
+            .ok_or(anyhow!("Valid filename from {source:?} not found."))?;
+        let content = fs::read(source).map_err(|e| anyhow!("{}: {e}", source.display()))?;
+        let file = File::new(name, directory, u16::from(member), crc16(&content))?;
+        Ok(Self { file, content })
+    }
+}
+
+// Checks that the files of a load are the ones listed in its header.
+fn check_load_files(load: &ManifestLoad, header: &Source, files: &[Source]) -> anyhow::Result<()> {
+    let listed = LoadHeaderFiles::from_bytes(&header.content)
+        .map_err(|e| anyhow!("{}: {e}", load.header.display()))?;
+    for name in listed
+        .get_data_files()
+        .iter()
+        .chain(listed.get_support_files())
+    {
+        if !files.iter().any(|x| x.file.get_file_name() == *name) {
+            return Err(anyhow!(
+                "{name} is listed in {} but missing from the files of load {}.",
+                load.header.display(),
+                load.load_pn
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Writes the media set described by `manifest` to `out`: the files of the
+/// loads, `LOADS.LUM` and a `FILES.LUM` with the CRCs of the files written.
+///
+/// A media set with one member is written directly to `out`, otherwise every
+/// member is written to `out/MEDIA<n>`. `out` must be empty or missing unless
+/// `force` is set.
+pub fn build_media_set(manifest: &Path, out: &Path, force: bool) -> anyhow::Result<Vec<BuiltFile>> {
+    if !force && out.exists() && fs::read_dir(out)?.next().is_some() {
+        return Err(anyhow!(
+            "{} is not empty, pass --force to write into it.",
+            out.display()
+        ));
+    }
+    let base = manifest.parent().unwrap_or(Path::new(""));
+    let manifest = read_manifest(manifest)?;
+    let format_version: FormatVersion = manifest
+        .format_version
+        .parse()
+        .map_err(|e| anyhow!("{e}"))?;
+    if !format_version.is_media_file() {
+        return Err(anyhow!(
+            "{format_version} is not a media file format version."
+        ));
+    }
+    let media_set_pn = PartNumber::new(manifest.media_set_pn.as_str());
+    if manifest.members == 0 {
+        return Err(anyhow!("A media set has at least one member."));
+    }
+    let check_member = |member: u8| {
+        if member == 0 || member > manifest.members {
+            return Err(anyhow!(
+                "Member {member} is not part of a media set with {} members.",
+                manifest.members
+            ));
+        }
+        Ok(())
+    };
+
+    let mut loads = Vec::new();
+    let mut sources = Vec::new();
+    for load in &manifest.loads {
+        check_member(load.member)?;
+        let header = Source::read(&base.join(&load.header), &load.directory, load.member)?;
+        let files = load
+            .files
+            .iter()
+            .map(|x| Source::read(&base.join(x), &load.directory, load.member))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        check_load_files(load, &header, &files)?;
+        let target_hw_ids = load
+            .target_hw_ids
+            .iter()
+            .map(|x| TargetHW::new(x))
+            .collect::<Result<_, _>>()?;
+        loads.push(Lsp::new(
+            &PartNumber::new(load.load_pn.as_str()),
+            &header.file.get_file_name(),
+            u16::from(load.member),
+            target_hw_ids,
+        )?);
+        sources.push(header);
+        sources.extend(files);
+    }
+    for file in &manifest.files {
+        check_member(file.member)?;
+        sources.push(Source::read(
+            &base.join(&file.source),
+            &file.directory,
+            file.member,
+        )?);
+    }
+
+    if format_version == FormatVersion::Arinc665_3Media {
+        for source in &mut sources {
+            let mut hasher = CheckValueHasher::new(CheckValueType::Crc32)?;
+            hasher.update(&source.content);
+            source
+                .file
+                .set_check_value(CheckValueType::Crc32, &hasher.finish())?;
+        }
+    }
+
+    // The same pathname can't store two files, even on different members.
+    let mut paths = BTreeMap::new();
+    for source in &sources {
+        let path = get_relative_path(&source.file)?;
+        if let Some(x) = paths.insert(path.clone(), source) {
+            return Err(anyhow!(
+                "{} is stored twice, on media {} and {}.",
+                path.display(),
+                x.file.get_member_sequence_number(),
+                source.file.get_member_sequence_number()
+            ));
+        }
+    }
+
+    let files: Vec<File> = sources.iter().map(|x| x.file.clone()).collect();
+    let mut built = Vec::new();
+    for member in 1..=manifest.members {
+        let root = if manifest.members == 1 {
+            out.to_path_buf()
+        } else {
+            out.join(format!("MEDIA{member}"))
+        };
+        let media_set = MediaSetInfo::new(&media_set_pn, member, manifest.members)
+            .ok_or(anyhow!("The media set PN {media_set_pn} is too long."))?;
+        let loads_lum = LoadsLum::from_loads(format_version, media_set.clone(), loads.clone())?;
+        let files_lum = FilesLum::from_files(format_version, media_set, files.clone())?;
+
+        let mut write = |path: PathBuf, content: &[u8], file_crc: u16| -> anyhow::Result<()> {
+            let destination = root.join(&path);
+            if let Some(parent) = destination.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&destination, content)?;
+            built.push(BuiltFile {
+                path: destination.strip_prefix(out)?.to_path_buf(),
+                member_sequence_number: member,
+                file_crc: format!("0x{file_crc:04x}"),
+            });
+            Ok(())
+        };
+        write(
+            PathBuf::from("LOADS.LUM"),
+            &loads_lum.to_bytes()?,
+            loads_lum.get_file_crc(),
+        )?;
+        write(
+            PathBuf::from("FILES.LUM"),
+            &files_lum.to_bytes()?,
+            files_lum.get_file_crc(),
+        )?;
+        for (path, source) in &paths {
+            if source.file.get_member_sequence_number() == u16::from(member) {
+                write(path.clone(), &source.content, source.file.get_file_crc())?;
+            }
+        }
+    }
+    Ok(built)
+}
+
+#[cfg(test)]
+mod tests {
+    use arinc_explorer::media_set::MediaSet;
+    use arinc_explorer::validate::{validate, Severity};
+
+    use super::*;
+
+    // Writes a manifest with one load and one extra file, the header lists no
+    // files.
+    fn write_manifest(dir: &Path, format_version: &str) -> PathBuf {
+        let header: Vec<u8> = [0, 14, 0x8003, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+            .iter()
+            .flat_map(|x: &u16| x.to_be_bytes())
+            .collect();
+        fs::write(dir.join("LOAD.LUH"), header).unwrap();
+        fs::write(dir.join("README.TXT"), b"readme").unwrap();
+        let manifest = dir.join("manifest.toml");
+        fs::write(
+            &manifest,
+            format!(
+                "media_set_pn = \"ABC1813001\"\nformat_version = \
+                 \"{format_version}\"\n[[loads]]\nload_pn = \"ABC1813001\"\nheader = \
+                 \"LOAD.LUH\"\ntarget_hw_ids = [\"HW\"]\ndirectory = \
+                 \"\\\\LOAD\\\\\"\n[[files]]\nsource = \"README.TXT\"\n"
+            ),
+        )
+        .unwrap();
+        manifest
+    }
+
+    #[test]
+    fn test_build_media_set() {
+        for format_version in ["ARINC665-2", "ARINC665-3 media"] {
+            let dir = tempfile::tempdir().unwrap();
+            let manifest = write_manifest(dir.path(), format_version);
+            let out = dir.path().join("out");
+
+            let built = build_media_set(&manifest, &out, false).unwrap();
+            assert_eq!(built.len(), 4);
+            let media_set = MediaSet::open(&out).unwrap();
+            let errors = validate(&media_set)
+                .into_iter()
+                .filter(|x| x.severity == Severity::Error)
+                .collect::<Vec<_>>();
+            assert!(errors.is_empty(), "{format_version}: {errors:?}");
+
+            let files = media_set.get_files_lum().get_media_set_files();
+            assert_eq!(files.len(), 2);
+            for file in files {
+                let check_value = file.get_check_value();
+                if format_version == "ARINC665-3 media" {
+                    let content = fs::read(media_set.get_file_path(file).unwrap()).unwrap();
+                    let mut hasher = CheckValueHasher::new(CheckValueType::Crc32).unwrap();
+                    hasher.update(&content);
+                    assert_eq!(check_value, Some((CheckValueType::Crc32, hasher.finish())));
+                } else {
+                    assert_eq!(check_value, None);
+                }
+            }
+
+            // The media set isn't written again over a non-empty directory.
+            assert!(build_media_set(&manifest, &out, false).is_err());
+            assert!(build_media_set(&manifest, &out, true).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_build_media_set_format_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = write_manifest(dir.path(), "ARINC665-3 load");
+        let error = build_media_set(&manifest, &dir.path().join("out"), false).unwrap_err();
+        assert!(error.to_string().contains("ARINC665-3 load"));
+        assert!(!dir.path().join("out").exists());
+    }
+}
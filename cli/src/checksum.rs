@@ -0,0 +1,317 @@
+//! The CRCs and check values computed by the `checksum` command.
+
+use std::fmt::{self, Display, Formatter};
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use arinc_explorer::check_value::{CheckValueHasher, CheckValueType};
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::UsageError;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Algorithm {
+    /// The CRC-16 of the `file_crc` fields.
+    Crc16,
+    /// The CRC-32 of the load CRCs.
+    Crc32,
+    /// A CRC-16 check value.
+    CheckValueCrc16,
+    /// A CRC-32 check value.
+    CheckValueCrc32,
+}
+impl Algorithm {
+    fn check_value_type(self) -> CheckValueType {
+        match self {
+            Algorithm::Crc16 | Algorithm::CheckValueCrc16 => CheckValueType::Crc16,
+            Algorithm::Crc32 | Algorithm::CheckValueCrc32 => CheckValueType::Crc32,
+        }
+    }
+
+    // Formats the value as the field storing it.
+    fn format(self, value: &[u8]) -> String {
+        let hex: String = value.iter().map(|x| format!("{x:02x}")).collect();
+        match self {
+            Algorithm::Crc16 | Algorithm::Crc32 => format!("0x{hex}"),
+            Algorithm::CheckValueCrc16 | Algorithm::CheckValueCrc32 => format!(
+                "check_value_length={} check_value_type={} check_value=0x{hex}",
+                value.len(),
+                u16::from(self.check_value_type())
+            ),
+        }
+    }
+}
+
+/// A range of bytes, `START..END` with END excluded. Either bound can be
+/// omitted and negative bounds count from the end of the file, e.g. `..-2`
+/// is everything but the file CRC of a LUM file.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ByteRange {
+    start: Option<i64>,
+    end: Option<i64>,
+}
+impl ByteRange {
+    // Returns the bounds within a file of `length` bytes.
+    fn resolve(self, length: u64) -> Result<(u64, u64), UsageError> {
+        let resolve = |bound: Option<i64>, default: u64| -> Result<u64, UsageError> {
+            match bound {
+                None => Ok(default),
+                Some(x) if x < 0 => {
+                    length
+                        .checked_sub(x.unsigned_abs())
+                        .ok_or(UsageError(format!(
+                            "{x} is before the beginning of the file."
+                        )))
+                }
+                Some(x) => Ok(x.unsigned_abs()),
+            }
+        };
+        let (start, end) = (resolve(self.start, 0)?, resolve(self.end, length)?);
+        if start > end || end > length {
+            return Err(UsageError(format!(
+                "The range {self} is outside the {length} bytes of the file."
+            )));
+        }
+        Ok((start, end))
+    }
+}
+impl FromStr for ByteRange {
+    type Err = String;
+
+    /// Parses decimal or `0x` prefixed hexadecimal bounds.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once("..")
+            .ok_or(format!("Expected START..END, found {s:?}."))?;
+        let bound = |x: &str| -> Result<Option<i64>, String> {
+            let (negative, digits) = match x.strip_prefix('-') {
+                Some(digits) => (true, digits),
+                None => (false, x),
+            };
+            let value = match digits.strip_prefix("0x") {
+                _ if digits.is_empty() => return Ok(None),
+                Some(hex) => i64::from_str_radix(hex, 16),
+                None => digits.parse(),
+            }
+            .map_err(|e| format!("{x:?}: {e}"))?;
+            Ok(Some(if negative { -value } else { value }))
+        };
+        Ok(Self {
+            start: bound(start)?,
+            end: bound(end)?,
+        })
+    }
+}
+impl Display for ByteRange {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if let Some(start) = self.start {
+            write!(f, "{start}")?;
+        }
+        write!(f, "..")?;
+        if let Some(end) = self.end {
+            write!(f, "{end}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The value computed over a file, or over several files with `--combined`.
+#[derive(Debug, Serialize)]
+pub struct Checksum {
+    /// The files, joined with ` + ` when they are combined.
+    path: String,
+    /// The byte range of every file, END excluded.
+    range: String,
+    value: String,
+    /// Whether the value matches `--expected`.
+    matches: Option<bool>,
+}
+impl Checksum {
+    #[must_use]
+    pub fn is_mismatch(&self) -> bool {
+        self.matches == Some(false)
+    }
+}
+impl Display for Checksum {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} [{}]: {}", self.path, self.range, self.value)?;
+        match self.matches {
+            Some(true) => write!(f, " (matches)"),
+            Some(false) => write!(f, " (MISMATCH)"),
+            None => Ok(()),
+        }
+    }
+}
+
+// Feeds the bytes of `range` of the file to the hasher, in chunks.
+fn update(hasher: &mut CheckValueHasher, path: &Path, range: ByteRange) -> anyhow::Result<String> {
+    let mut file = File::open(path).map_err(|e| anyhow!("{}: {e}", path.display()))?;
+    let (start, end) = range.resolve(file.metadata()?.len())?;
+    file.seek(SeekFrom::Start(start))?;
+    let mut reader = io::BufReader::new(file.take(end - start));
+    let mut buf = [0; 64 * 1024];
+    loop {
+        let read = reader.read(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buf[..read]);
+    }
+    Ok(format!("{start}..{end}"))
+}
+
+// Parses an expected value, with or without the `0x` prefix.
+fn parse_expected(expected: &str) -> Result<Vec<u8>, UsageError> {
+    let error = || UsageError(format!("{expected:?} is not a hexadecimal value."));
+    let hex = expected.strip_prefix("0x").unwrap_or(expected);
+    if hex.is_empty() || hex.len() % 2 != 0 || !hex.is_ascii() {
+        return Err(error());
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+        .collect::<Result<_, _>>()
+        .map_err(|_| error())
+}
+
+/// Computes `algorithm` over `range` of every file, or over the ranges of all
+/// the files in order if `combined`, and compares the values to `expected`.
+pub fn checksum(
+    paths: &[PathBuf],
+    algorithm: Algorithm,
+    range: ByteRange,
+    combined: bool,
+    expected: Option<&str>,
+) -> anyhow::Result<Vec<Checksum>> {
+    let expected = expected.map(parse_expected).transpose()?;
+    let checksum = |path: String, range: String, hasher: CheckValueHasher| {
+        let value = hasher.finish();
+        Checksum {
+            path,
+            range,
+            matches: expected.as_ref().map(|x| *x == value),
+            value: algorithm.format(&value),
+        }
+    };
+    if combined {
+        let mut hasher = CheckValueHasher::new(algorithm.check_value_type())?;
+        let ranges = paths
+            .iter()
+            .map(|path| update(&mut hasher, path, range))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let path = paths
+            .iter()
+            .map(|x| x.display().to_string())
+            .collect::<Vec<_>>()
+            .join(" + ");
+        return Ok(vec![checksum(path, ranges.join(" + "), hasher)]);
+    }
+    paths
+        .iter()
+        .map(|path| {
+            let mut hasher = CheckValueHasher::new(algorithm.check_value_type())?;
+            let range = update(&mut hasher, path, range)?;
+            Ok(checksum(path.display().to_string(), range, hasher))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(s: &str) -> ByteRange {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_byte_range() {
+        assert_eq!(range(".."), ByteRange::default());
+        assert_eq!(
+            range("0x10..-2"),
+            ByteRange {
+                start: Some(16),
+                end: Some(-2)
+            }
+        );
+        assert_eq!(range("-4..").to_string(), "-4..");
+        assert!("10".parse::<ByteRange>().is_err());
+        assert!("a..b".parse::<ByteRange>().is_err());
+        assert!("0x..".parse::<ByteRange>().is_err());
+
+        assert_eq!(range("..").resolve(100).unwrap(), (0, 100));
+        assert_eq!(range("..-2").resolve(100).unwrap(), (0, 98));
+        assert_eq!(range("-4..").resolve(100).unwrap(), (96, 100));
+        assert_eq!(range("0x10..0x20").resolve(100).unwrap(), (16, 32));
+        assert_eq!(range("10..10").resolve(100).unwrap(), (10, 10));
+        assert!(range("10..5").resolve(100).is_err());
+        assert!(range("..101").resolve(100).is_err());
+        assert!(range("-101..").resolve(100).is_err());
+    }
+
+    #[test]
+    fn test_parse_expected() {
+        assert_eq!(parse_expected("0xee35").unwrap(), [0xee, 0x35]);
+        assert_eq!(parse_expected("EE35").unwrap(), [0xee, 0x35]);
+        for expected in ["", "0x", "0xe", "zz", "0xé1"] {
+            assert!(parse_expected(expected).is_err(), "{expected:?}");
+        }
+    }
+
+    #[test]
+    fn test_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = [dir.path().join("A"), dir.path().join("B")];
+        std::fs::write(&paths[0], b"1234").unwrap();
+        std::fs::write(&paths[1], b"56789").unwrap();
+        let all = dir.path().join("ALL");
+        std::fs::write(&all, b"123456789").unwrap();
+
+        // The CRC-32 of "123456789" as computed for the load CRC.
+        let single = checksum(
+            std::slice::from_ref(&all),
+            Algorithm::Crc32,
+            ByteRange::default(),
+            false,
+            None,
+        )
+        .unwrap();
+        let combined = checksum(
+            &paths,
+            Algorithm::Crc32,
+            ByteRange::default(),
+            true,
+            Some(&single[0].value),
+        )
+        .unwrap();
+        assert_eq!(combined.len(), 1);
+        assert_eq!(combined[0].value, single[0].value);
+        assert_eq!(combined[0].range, "0..4 + 0..5");
+        assert_eq!(combined[0].matches, Some(true));
+
+        let separate = checksum(
+            &paths,
+            Algorithm::CheckValueCrc16,
+            range("1.."),
+            false,
+            Some("0x0000"),
+        )
+        .unwrap();
+        assert_eq!(separate.len(), 2);
+        assert_eq!(separate[1].range, "1..5");
+        assert!(separate[1]
+            .value
+            .starts_with("check_value_length=2 check_value_type=2 check_value=0x"));
+        assert!(separate.iter().all(Checksum::is_mismatch));
+
+        let error = checksum(&paths, Algorithm::Crc16, range("2..1"), false, None).unwrap_err();
+        assert!(error.is::<UsageError>());
+        let error =
+            checksum(&paths, Algorithm::Crc16, range(".."), false, Some("xyz")).unwrap_err();
+        assert!(error.is::<UsageError>());
+    }
+}
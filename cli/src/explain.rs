@@ -0,0 +1,223 @@
+//! The annotated hex dump printed by the `explain` command.
+
+use std::fmt::Write;
+
+use arinc_explorer::field_map::Field;
+use serde::Serialize;
+
+use crate::output::Style;
+
+// The number of bytes in a line of the dump.
+const LINE_LENGTH: usize = 16;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    Field,
+    /// Bytes within the declared file length which aren't part of a field.
+    Unparsed,
+    /// Bytes after the declared file length.
+    Trailing,
+}
+
+/// A range of bytes of the file, with the field it belongs to.
+#[derive(Debug, Serialize)]
+pub struct Range {
+    offset: usize,
+    length: usize,
+    kind: Kind,
+    field: Option<String>,
+    value: Option<String>,
+    padding: usize,
+    pointer_target: Option<usize>,
+    /// The field starting where the pointer lands.
+    pointer_target_field: Option<String>,
+    bytes: String,
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|x| format!("{x:02x}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Splits the bytes of a file in the ranges of its fields, followed by the
+/// unparsed and trailing bytes. `fields` is the field map of the parsed file
+/// and `file_length` the length in bytes declared in its header.
+#[must_use]
+pub fn explain(bytes: &[u8], fields: &[Field], file_length: usize) -> Vec<Range> {
+    let mut ranges: Vec<Range> = fields
+        .iter()
+        .map(|field| {
+            let pointer_target = field.get_pointer_target();
+            Range {
+                offset: field.offset,
+                length: field.length,
+                kind: Kind::Field,
+                field: Some(field.name.clone()),
+                value: Some(field.value.to_string()),
+                padding: field.padding,
+                pointer_target,
+                pointer_target_field: pointer_target.and_then(|target| {
+                    fields
+                        .iter()
+                        .find(|x| x.offset == target)
+                        .map(|x| x.name.clone())
+                }),
+                bytes: hex(bytes
+                    .get(field.offset..field.offset + field.length)
+                    .unwrap_or(&field.raw)),
+            }
+        })
+        .collect();
+
+    let parsed = fields.last().map_or(0, |x| x.offset + x.length);
+    let mut push_bytes = |start: usize, end: usize, kind: Kind| {
+        if start < end {
+            ranges.push(Range {
+                offset: start,
+                length: end - start,
+                kind,
+                field: None,
+                value: None,
+                padding: 0,
+                pointer_target: None,
+                pointer_target_field: None,
+                bytes: hex(&bytes[start..end]),
+            });
+        }
+    };
+    let end = file_length.clamp(parsed, bytes.len().max(parsed));
+    push_bytes(
+        parsed.min(bytes.len()),
+        end.min(bytes.len()),
+        Kind::Unparsed,
+    );
+    push_bytes(end.min(bytes.len()), bytes.len(), Kind::Trailing);
+    ranges
+}
+
+fn write_label(out: &mut String, range: &Range, color: bool) {
+    match range.kind {
+        Kind::Field => {
+            let field = range.field.as_deref().unwrap_or_default();
+            let value = range.value.as_deref().unwrap_or_default();
+            let _ = write!(out, "{} = {value}", Style::Bold.paint(field, color));
+            if range.padding > 0 {
+                let _ = write!(out, " (+{} padding)", range.padding);
+            }
+            if let Some(target) = range.pointer_target {
+                let _ = write!(
+                    out,
+                    " → 0x{target:08x} {}",
+                    range
+                        .pointer_target_field
+                        .as_deref()
+                        .unwrap_or("outside any field")
+                );
+            }
+        }
+        Kind::Unparsed => out.push_str(&Style::Bad.paint("unparsed", color)),
+        Kind::Trailing => {
+            out.push_str(&Style::Bad.paint("trailing, past the file length", color));
+        }
+    }
+}
+
+/// Renders the ranges as a hex dump with one label per range. The padding
+/// bytes are highlighted, the unparsed and trailing bytes are shown in red.
+#[must_use]
+pub fn render(ranges: &[Range], color: bool) -> String {
+    let mut out = String::new();
+    for range in ranges {
+        let bytes: Vec<&str> = range.bytes.split(' ').filter(|x| !x.is_empty()).collect();
+        let padding_start = bytes.len().saturating_sub(range.padding);
+        let mut lines: Vec<&[&str]> = bytes.chunks(LINE_LENGTH).collect();
+        // Empty strings still get their line.
+        if lines.is_empty() {
+            lines.push(&[]);
+        }
+        for (line, chunk) in lines.into_iter().enumerate() {
+            let start = line * LINE_LENGTH;
+            let dump = chunk
+                .iter()
+                .enumerate()
+                .map(|(i, x)| {
+                    let style = match range.kind {
+                        Kind::Field if start + i >= padding_start => Style::Highlight,
+                        Kind::Field => Style::Plain,
+                        Kind::Unparsed | Kind::Trailing => Style::Bad,
+                    };
+                    style.paint(x, color)
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            // The escape codes don't take space on the terminal.
+            let width = LINE_LENGTH * 3 - 1 + dump.len() - (chunk.len() * 3).saturating_sub(1);
+            let mut text = format!("{:08x}  {dump:width$}  ", range.offset + start);
+            if line == 0 {
+                write_label(&mut text, range, color);
+            }
+            let _ = writeln!(out, "{}", text.trim_end());
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use arinc_explorer::arinc_file::ArincFile;
+    use arinc_explorer::loads::LoadsLum;
+
+    use super::*;
+
+    #[test]
+    fn test_explain() {
+        let mut bytes = include_bytes!("../../test-data/LOADS.LUM").to_vec();
+        let loads_lum = LoadsLum::from_bytes(&bytes).unwrap();
+        let fields = loads_lum.get_field_map().unwrap();
+        bytes.extend([0xaa, 0xbb]);
+        let ranges = explain(&bytes, &fields, bytes.len() - 2);
+
+        // The ranges follow each other up to the end of the file.
+        let mut offset = 0;
+        for range in &ranges {
+            assert_eq!(range.offset, offset);
+            offset += range.length;
+        }
+        assert_eq!(offset, bytes.len());
+        assert_eq!(ranges[0].field.as_deref(), Some("file_length"));
+        let last = ranges.last().unwrap();
+        assert_eq!(last.kind, Kind::Trailing);
+        assert_eq!(last.bytes, "aa bb");
+        let pointer = ranges
+            .iter()
+            .find(|x| x.field.as_deref() == Some("pointer_to_number_of_loads"))
+            .unwrap();
+        assert_eq!(
+            pointer.pointer_target_field.as_deref(),
+            Some("number_of_loads")
+        );
+
+        // The bytes past the fields within the file length are unparsed.
+        let ranges = explain(&bytes, &fields[..fields.len() - 1], bytes.len());
+        assert_eq!(ranges.last().unwrap().kind, Kind::Unparsed);
+        assert_eq!(ranges.last().unwrap().length, 4);
+    }
+
+    #[test]
+    fn test_render() {
+        let bytes = include_bytes!("../../test-data/LOADS.LUM");
+        let loads_lum = LoadsLum::from_bytes(bytes).unwrap();
+        let ranges = explain(bytes, &loads_lum.get_field_map().unwrap(), bytes.len());
+        let rendered = render(&ranges, false);
+        assert!(rendered.starts_with(
+            "00000000  00 00 00 27                                      file_length = 39\n"
+        ));
+        assert!(rendered.contains("→ 0x00000020 number_of_loads"));
+        assert!(!rendered.contains('\x1b'));
+        assert!(render(&ranges, true).contains('\x1b'));
+    }
+}
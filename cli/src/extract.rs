@@ -0,0 +1,302 @@
+//! Copies the files of a load out of a media set.
+
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use arinc_explorer::arinc_file::MediaSetFile;
+use arinc_explorer::crc::crc16;
+use arinc_explorer::files::File;
+use arinc_explorer::load_header::LoadHeaderFiles;
+use arinc_explorer::media_set::{get_relative_path, MediaSet};
+use serde::Serialize;
+
+/// A file copied by [`extract_load`].
+#[derive(Debug, Serialize)]
+pub struct ExtractedFile {
+    /// The path relative to the output directory.
+    path: PathBuf,
+    member_sequence_number: u16,
+    file_crc: String,
+}
+impl Display for ExtractedFile {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} from media {}, CRC {}",
+            self.path.display(),
+            self.member_sequence_number,
+            self.file_crc
+        )
+    }
+}
+
+struct Members(Vec<MediaSet>);
+impl Members {
+    // Finds a file by name. A name listed more than once is looked up in
+    // `pathname`, the directory of the load header.
+    fn find_file(&self, name: &str, pathname: Option<&str>) -> anyhow::Result<&File> {
+        let files = self.0[0]
+            .get_files_lum()
+            .get_media_set_files()
+            .iter()
+            .filter(|x| x.get_file_name() == name)
+            .collect::<Vec<_>>();
+        if let [file] = files.as_slice() {
+            return Ok(file);
+        }
+        if files.is_empty() {
+            return Err(anyhow!("{name} is not listed in FILES.LUM."));
+        }
+        match files
+            .iter()
+            .filter(|x| Some(x.get_file_pathname().as_str()) == pathname)
+            .collect::<Vec<_>>()
+            .as_slice()
+        {
+            [file] => Ok(file),
+            _ => Err(anyhow!(
+                "{name} is listed {} times in FILES.LUM, in {}.",
+                files.len(),
+                files
+                    .iter()
+                    .map(|x| x.get_file_pathname())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+
+    // Reads a file from the member which stores it and checks its CRC.
+    fn read(&self, file: &File) -> anyhow::Result<Vec<u8>> {
+        let member = file.get_member_sequence_number();
+        let media_set = self
+            .0
+            .iter()
+            .find(|x| u16::from(x.get_loads_lum().get_media_sequence_number()) == member)
+            .ok_or(anyhow!(
+                "{} is on media {member}, pass the root directory of that media too.",
+                file.get_file_name()
+            ))?;
+        let content = fs::read(media_set.get_file_path(file)?)?;
+        if crc16(&content) != file.get_file_crc() {
+            return Err(anyhow!(
+                "The CRC of {} doesn't match 0x{:04x} listed in FILES.LUM.",
+                file.get_file_name(),
+                file.get_file_crc()
+            ));
+        }
+        Ok(content)
+    }
+}
+
+// A directory next to `out` where the files are written while they are
+// verified, removed when dropped.
+struct Staging(PathBuf);
+impl Staging {
+    fn new(out: &Path) -> anyhow::Result<Self> {
+        let name = out
+            .file_name()
+            .map_or("extract".into(), |x| x.to_string_lossy());
+        let path = out
+            .parent()
+            .unwrap_or(Path::new(""))
+            .join(format!(".{name}.partial"));
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::create_dir(&path).map_err(|e| anyhow!("{}: {e}", path.display()))?;
+        Ok(Self(path))
+    }
+
+    fn write(&self, path: &Path, content: &[u8]) -> anyhow::Result<()> {
+        let destination = self.0.join(path);
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(fs::write(destination, content)?)
+    }
+}
+impl Drop for Staging {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Copies the header, data and support files of the load `load_pn` to
+/// `out`, keeping their pathnames. The media can be any members of the same
+/// media set, the files are read from the member listed in `FILES.LUM`.
+///
+/// Every file is read once and checked against its CRC while it's copied to
+/// a staging directory next to `out`. The files are only moved to `out` once
+/// all of them match, so a mismatch leaves `out` untouched.
+pub fn extract_load(
+    media_roots: &[PathBuf],
+    load_pn: &str,
+    out: &Path,
+) -> anyhow::Result<Vec<ExtractedFile>> {
+    let members = Members(
+        media_roots
+            .iter()
+            .map(|x| MediaSet::open(x))
+            .collect::<Result<_, _>>()?,
+    );
+    let first = members.0.first().ok_or(anyhow!("Missing media root."))?;
+    if let Some(x) = members
+        .0
+        .iter()
+        .find(|x| x.get_loads_lum().get_media_set_pn() != first.get_loads_lum().get_media_set_pn())
+    {
+        return Err(anyhow!(
+            "{} is not part of media set {}.",
+            x.get_root().display(),
+            first.get_loads_lum().get_media_set_pn()
+        ));
+    }
+
+    let load = first
+        .get_loads_lum()
+        .get_loads()
+        .iter()
+        .find(|x| x.get_load_pn().as_str() == load_pn)
+        .ok_or(anyhow!("Load {load_pn} is not listed in LOADS.LUM."))?;
+    let header = members.find_file(&load.get_header_filename(), None)?;
+    let header_content = members.read(header)?;
+    let load_files = LoadHeaderFiles::from_bytes(&header_content)
+        .map_err(|e| anyhow!("{}: {e}", header.get_file_name()))?;
+    let directory = header.get_file_pathname();
+    let files = load_files
+        .get_data_files()
+        .iter()
+        .chain(load_files.get_support_files())
+        .map(|name| members.find_file(name, Some(&directory)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let staging = Staging::new(out)?;
+    let mut extracted = Vec::new();
+    let mut stage = |file: &File, content: &[u8]| -> anyhow::Result<()> {
+        let path = get_relative_path(file)?;
+        staging.write(&path, content)?;
+        extracted.push(ExtractedFile {
+            path,
+            member_sequence_number: file.get_member_sequence_number(),
+            file_crc: format!("0x{:04x}", file.get_file_crc()),
+        });
+        Ok(())
+    };
+    stage(header, &header_content)?;
+    for file in files {
+        stage(file, &members.read(file)?)?;
+    }
+
+    for file in &extracted {
+        let destination = out.join(&file.path);
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::rename(staging.0.join(&file.path), destination)?;
+    }
+    Ok(extracted)
+}
+
+#[cfg(test)]
+mod tests {
+    use arinc_explorer::arinc_file::ArincFile;
+    use arinc_explorer::files::FilesLum;
+    use arinc_explorer::format_version::FormatVersion;
+    use arinc_explorer::header::MediaSetInfo;
+    use arinc_explorer::loads::{LoadsLum, Lsp, TargetHW};
+    use arinc_explorer::part_number::PartNumber;
+
+    use super::*;
+
+    // An ARINC 665-2 load header with one data file and one support file.
+    fn load_header(data_file: &str, support_file: &str) -> Vec<u8> {
+        let list = |name: &str| {
+            let mut words = vec![1, 0, u16::try_from(name.len()).unwrap()];
+            words.extend(
+                name.as_bytes()
+                    .chunks(2)
+                    .map(|x| u16::from_be_bytes([x[0], x.get(1).copied().unwrap_or_default()])),
+            );
+            words
+        };
+        let data = list(data_file);
+        let support = list(support_file);
+        let support_pointer = 14 + u16::try_from(data.len()).unwrap();
+        let mut words = vec![0, 0, 0x8003, 0, 0, 0, 0, 0, 0, 14, 0, support_pointer, 0, 0];
+        words.extend(data);
+        words.extend(support);
+        words.iter().flat_map(|x| x.to_be_bytes()).collect()
+    }
+
+    // Writes a media set with the load ABC1813001 whose header is in
+    // `\LOAD\`. `DATA.BIN` is stored in `\LOAD\` and `\OTHER\`.
+    fn write_media_set(root: &Path) {
+        let pn = PartNumber::new("ABC1813001");
+        let media_set = MediaSetInfo::new(&pn, 1, 1).unwrap();
+        let content = [
+            (
+                "LOAD.LUH",
+                "\\LOAD\\",
+                load_header("DATA.BIN", "SUPPORT.BIN"),
+            ),
+            ("DATA.BIN", "\\LOAD\\", b"data".to_vec()),
+            ("DATA.BIN", "\\OTHER\\", b"other data".to_vec()),
+            ("SUPPORT.BIN", "\\LOAD\\", b"support".to_vec()),
+        ];
+        let mut files = Vec::new();
+        for (name, pathname, content) in content {
+            let directory = root.join(pathname.trim_matches('\\'));
+            fs::create_dir_all(&directory).unwrap();
+            fs::write(directory.join(name), &content).unwrap();
+            files.push(File::new(name, pathname, 1, crc16(&content)).unwrap());
+        }
+        let loads = vec![Lsp::new(&pn, "LOAD.LUH", 1, vec![TargetHW::new("HW").unwrap()]).unwrap()];
+        let loads_lum =
+            LoadsLum::from_loads(FormatVersion::Arinc665_2, media_set.clone(), loads).unwrap();
+        let files_lum = FilesLum::from_files(FormatVersion::Arinc665_2, media_set, files).unwrap();
+        fs::write(root.join("LOADS.LUM"), loads_lum.to_bytes().unwrap()).unwrap();
+        fs::write(root.join("FILES.LUM"), files_lum.to_bytes().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_extract_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let media = dir.path().join("media");
+        write_media_set(&media);
+        let out = dir.path().join("out");
+
+        let extracted = extract_load(std::slice::from_ref(&media), "ABC1813001", &out).unwrap();
+        assert_eq!(
+            extracted.iter().map(|x| x.path.clone()).collect::<Vec<_>>(),
+            [
+                Path::new("LOAD/LOAD.LUH"),
+                Path::new("LOAD/DATA.BIN"),
+                Path::new("LOAD/SUPPORT.BIN")
+            ]
+        );
+        // The data file is the one in the directory of the header.
+        assert_eq!(fs::read(out.join("LOAD/DATA.BIN")).unwrap(), b"data");
+        assert!(!out.join("OTHER").exists());
+        assert!(!dir.path().join(".out.partial").exists());
+
+        assert!(extract_load(&[media], "XYZ", &dir.path().join("none")).is_err());
+    }
+
+    #[test]
+    fn test_extract_load_crc_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let media = dir.path().join("media");
+        write_media_set(&media);
+        fs::write(media.join("LOAD/SUPPORT.BIN"), b"damaged").unwrap();
+        let out = dir.path().join("out");
+
+        let error = extract_load(&[media], "ABC1813001", &out).unwrap_err();
+        assert!(error.to_string().contains("SUPPORT.BIN"));
+        // Nothing is written when a file doesn't match its CRC.
+        assert!(!out.exists());
+        assert!(!dir.path().join(".out.partial").exists());
+    }
+}
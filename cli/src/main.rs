@@ -1,23 +1,281 @@
-use std::path::PathBuf;
-use std::process;
+mod build;
+mod checksum;
+mod explain;
+mod extract;
+mod output;
+mod scan;
+mod tree;
+
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::{fs, process};
 
 use anyhow::anyhow;
+use arinc_explorer::arinc_file::ArincFile;
+use arinc_explorer::convert::ConversionWarning;
+use arinc_explorer::diff::{diff_files_lum, diff_loads_lum, diff_media_sets, Difference};
+use arinc_explorer::error::{FilesLumError, LoadsLumError, MediaSetError, UserDataError};
+use arinc_explorer::export::Annotated;
 use arinc_explorer::files::FilesLum;
+use arinc_explorer::format_version::FormatVersion;
 use arinc_explorer::loads::LoadsLum;
-use clap::Parser;
-use exitcode::{OK, SOFTWARE};
+use arinc_explorer::manufacturer::{
+    DisplayWithManufacturers,
+    ManufacturerRegistry,
+    WithManufacturers,
+};
+use arinc_explorer::media_set::MediaSet;
+use arinc_explorer::repair::{repair_files_lum, repair_loads_lum, RepairedValue};
+use arinc_explorer::schema::SchemaType;
+use arinc_explorer::user_data::{UserDataDecoder, UserDataRegistry};
+use arinc_explorer::validate::{validate, Finding, Severity};
+use clap::error::ErrorKind;
+use clap::{ColorChoice, CommandFactory, Parser, Subcommand};
+use exitcode::{ExitCode, DATAERR, NOINPUT, OK, SOFTWARE, USAGE};
+use output::{error_message, print_csv, print_structured, use_color, Export, Format, Table};
+use serde::Serialize;
 
 #[derive(Clone, Parser, Debug)]
+#[command(subcommand_negates_reqs = true)]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// The LOADS.LUM or FILES.LUM to print, as with the `show` command.
+    #[arg(long, required = true)]
+    pub filename: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub show: ShowArgs,
+
+    /// File with extra manufacturer codes, one `MMM,Supplier name` per line.
+    #[arg(long, global = true)]
+    pub manufacturers: Option<PathBuf>,
+
+    /// The output format of every command.
+    #[arg(long, global = true, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+/// The options of the `show` command, also accepted with `--filename`.
+#[derive(Clone, clap::Args, Debug, Default, Eq, PartialEq)]
+pub struct ShowArgs {
+    /// Reject strings which don't conform to the ARINC 665 character sets.
     #[arg(long)]
-    pub filename: PathBuf,
+    pub strict: bool,
+
+    /// Decoder of the user defined data, one of `hex`, `ascii` or `tlv`. By
+    /// default the decoder is selected by media set PN prefix.
+    #[arg(long)]
+    pub user_data_decoder: Option<String>,
+
+    /// Decode the user defined data of the media sets whose PN starts with
+    /// PREFIX with the decoder NAME.
+    #[arg(long, value_name = "PREFIX=NAME")]
+    pub user_data_prefix: Vec<String>,
+
+    /// The table printed with `--format csv`, by default the loads of
+    /// LOADS.LUM and the files of FILES.LUM.
+    #[arg(long, value_enum)]
+    pub table: Option<Table>,
 }
 
+#[derive(Clone, Subcommand, Debug)]
+pub enum Command {
+    /// Print the content of LOADS.LUM or FILES.LUM.
+    Show {
+        /// The LOADS.LUM or FILES.LUM file to print.
+        filename: PathBuf,
+
+        #[command(flatten)]
+        options: ShowArgs,
+    },
+    /// Print the JSON Schema of the structured representation of a file type.
+    Schema {
+        /// One of `loads-lum` or `files-lum`.
+        schema_type: SchemaType,
+    },
+    /// Convert LOADS.LUM or FILES.LUM to another media file format version.
+    Convert {
+        /// The LOADS.LUM or FILES.LUM file to convert.
+        input: PathBuf,
+
+        /// The target format version, `ARINC665-1`, `ARINC665-2` or
+        /// `ARINC665-3 media` (`0xA004`).
+        #[arg(long)]
+        to: FormatVersion,
+
+        /// Where to write the converted file.
+        #[arg(long)]
+        output: PathBuf,
+    },
+    /// Run the structural, CRC and cross-reference checks of a media.
+    ///
+    /// The exit code is 0 if the media is valid, 1 if there are only
+    /// warnings, 65 if there are errors, including a LOADS.LUM or FILES.LUM
+    /// which doesn't parse, and 66 if one of them can't be read.
+    Validate {
+        /// The root directory of the media, with LOADS.LUM and FILES.LUM.
+        media_root: PathBuf,
+    },
+    /// Print the members, loads, target HW IDs and files of a media set as a
+    /// tree.
+    Tree {
+        /// The root directory of the media, with LOADS.LUM and FILES.LUM.
+        media_root: PathBuf,
+
+        /// Collapse the entries deeper than DEPTH into a count.
+        #[arg(long)]
+        depth: Option<usize>,
+
+        /// Colour the part numbers and the CRC status.
+        #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+        color: ColorChoice,
+    },
+    /// Print a hex dump of LOADS.LUM or FILES.LUM with the field each byte
+    /// belongs to.
+    ///
+    /// Only the files named LOADS.LUM or FILES.LUM have a field map, the load
+    /// headers and the other files can't be explained.
+    Explain {
+        /// The LOADS.LUM or FILES.LUM file to explain.
+        input: PathBuf,
+
+        /// Colour the field names, the padding and the unparsed bytes.
+        #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+        color: ColorChoice,
+    },
+    /// Compare two revisions of LOADS.LUM, of FILES.LUM or of a media.
+    ///
+    /// The exit code is 0 if there are no differences and 1 otherwise.
+    Diff {
+        /// The old LOADS.LUM, FILES.LUM or media root directory.
+        old: PathBuf,
+
+        /// The new file or media root directory, of the same type as OLD.
+        new: PathBuf,
+    },
+    /// Copy the header, data and support files of a load to a directory.
+    Extract {
+        /// The root directories of the media storing the files of the load.
+        #[arg(required = true)]
+        media_roots: Vec<PathBuf>,
+
+        /// The PN of the load to extract.
+        #[arg(long)]
+        load: String,
+
+        /// The directory where the files are copied, with their pathnames.
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Write a media set from a TOML or YAML manifest listing its loads and
+    /// files.
+    ///
+    /// LOADS.LUM and FILES.LUM are written on every member, with the CRCs of
+    /// the files read from the manifest.
+    Build {
+        /// The `.toml` or `.yaml` manifest.
+        manifest: PathBuf,
+
+        /// The media root, or the parent of the `MEDIA<n>` roots if the media
+        /// set has more than one member.
+        #[arg(long)]
+        out: PathBuf,
+
+        /// Write into `--out` even if it isn't empty.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Recompute the file length, pointers, string lengths and CRC of a
+    /// damaged LOADS.LUM or FILES.LUM and print the values changed.
+    Repair {
+        /// The LOADS.LUM or FILES.LUM file to repair.
+        input: PathBuf,
+
+        /// Where to write the repaired file.
+        #[arg(
+            long,
+            required_unless_present = "in_place",
+            conflicts_with = "in_place"
+        )]
+        output: Option<PathBuf>,
+
+        /// Overwrite INPUT with the repaired file.
+        #[arg(long)]
+        in_place: bool,
+    },
+    /// Compute the CRC or check value of files, as stored in the LUM and LUH
+    /// fields.
+    ///
+    /// With --expected, the exit code is 1 if a value doesn't match.
+    Checksum {
+        /// The files to read.
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+
+        #[arg(long, value_enum, default_value_t = checksum::Algorithm::Crc16)]
+        algorithm: checksum::Algorithm,
+
+        /// The bytes of every file to read, START..END with END excluded,
+        /// e.g. `..-2` for a LUM file without its CRC.
+        #[arg(long, default_value = "..")]
+        range: checksum::ByteRange,
+
+        /// Compute a single value over all the files, in order.
+        #[arg(long)]
+        combined: bool,
+
+        /// The expected value in hexadecimal, e.g. `0x29b1`.
+        #[arg(long)]
+        expected: Option<String>,
+    },
+    /// Find the media sets under a directory and print the PN, members,
+    /// number of loads, format version and validation status of each.
+    ///
+    /// The media which can't be read are printed as error rows and the exit
+    /// code is then 66.
+    Scan {
+        /// The directory to search.
+        root: PathBuf,
+    },
+}
+
+// The exit code of `validate` when the findings are at most warnings.
+const WARNINGS: ExitCode = 1;
+// The exit code of `diff` when there are differences.
+const DIFFERENT: ExitCode = 1;
+// The exit code of `checksum` when a value doesn't match the expected one.
+const MISMATCH: ExitCode = 1;
+
+/// An invalid argument found after parsing, e.g. a byte range outside the
+/// file. The exit code is `USAGE` instead of `SOFTWARE`.
+#[derive(Debug)]
+pub struct UsageError(pub String);
+impl Display for UsageError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl std::error::Error for UsageError {}
+
 fn main() {
     let args = Args::parse();
+    if args.command.is_some() && (args.filename.is_some() || args.show != ShowArgs::default()) {
+        Args::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--filename and its options can't be used with a subcommand, see `show`",
+            )
+            .exit();
+    }
 
     match run(args) {
-        Ok(()) => process::exit(OK),
+        Ok(code) => process::exit(code),
+        Err(e) if e.is::<UsageError>() => {
+            eprintln!("Usage error: {e}");
+            process::exit(USAGE);
+        }
         Err(e) => {
             eprintln!("Internal software error: {e}");
             process::exit(SOFTWARE);
@@ -25,24 +283,485 @@ fn main() {
     }
 }
 
-fn run(args: Args) -> anyhow::Result<()> {
-    let path = args.filename;
+fn run(args: Args) -> anyhow::Result<ExitCode> {
+    let mut registry = ManufacturerRegistry::default();
+    if let Some(manufacturers) = &args.manufacturers {
+        registry.extend_from_file(manufacturers)?;
+    }
+
+    match args.command {
+        Some(Command::Show { filename, options }) => {
+            show_file(&filename, &options, &registry, args.format)?;
+            return Ok(OK);
+        }
+        Some(Command::Schema { schema_type }) => {
+            let schema = schema_type.generate();
+            match args.format {
+                Format::Text | Format::Json => print_structured(Format::Json, &schema)?,
+                Format::Yaml => print_structured(Format::Yaml, &schema)?,
+                Format::Csv => {
+                    return Err(UsageError("The schema can't be printed as CSV.".into()).into())
+                }
+            }
+            return Ok(OK);
+        }
+        Some(Command::Convert { input, to, output }) => {
+            convert(&input, to, &output, args.format)?;
+            return Ok(OK);
+        }
+        Some(Command::Validate { media_root }) => {
+            return validate_media(&media_root, args.format);
+        }
+        Some(Command::Tree {
+            media_root,
+            depth,
+            color,
+        }) => {
+            print_tree(&media_root, &registry, depth, color, args.format)?;
+            return Ok(OK);
+        }
+        Some(Command::Explain { input, color }) => {
+            match input.file_name().and_then(|x| x.to_str()) {
+                Some("LOADS.LUM") => print_explain::<LoadsLum>(&input, color, args.format)?,
+                Some("FILES.LUM") => print_explain::<FilesLum>(&input, color, args.format)?,
+                _ => {
+                    return Err(UsageError(format!(
+                        "{input:?} can't be explained, only LOADS.LUM and FILES.LUM have a field \
+                         map."
+                    ))
+                    .into())
+                }
+            }
+            return Ok(OK);
+        }
+        Some(Command::Diff { old, new }) => return diff_paths(&old, &new, args.format),
+        Some(Command::Extract {
+            media_roots,
+            load,
+            out,
+        }) => {
+            let extracted = extract::extract_load(&media_roots, &load, &out)?;
+            match args.format {
+                Format::Text => {
+                    for file in &extracted {
+                        println!("{file}");
+                    }
+                }
+                Format::Json | Format::Yaml => print_structured(args.format, &extracted)?,
+                Format::Csv => print_csv(extracted)?,
+            }
+            return Ok(OK);
+        }
+        Some(Command::Build {
+            manifest,
+            out,
+            force,
+        }) => {
+            let built = build::build_media_set(&manifest, &out, force)?;
+            match args.format {
+                Format::Text => {
+                    for file in &built {
+                        println!("{file}");
+                    }
+                }
+                Format::Json | Format::Yaml => print_structured(args.format, &built)?,
+                Format::Csv => print_csv(built)?,
+            }
+            return Ok(OK);
+        }
+        Some(Command::Repair {
+            input,
+            output,
+            in_place,
+        }) => {
+            let output = if in_place {
+                input.clone()
+            } else {
+                output.ok_or(UsageError("Missing --output.".into()))?
+            };
+            repair(&input, &output, in_place, args.format)?;
+            return Ok(OK);
+        }
+        Some(Command::Checksum {
+            files,
+            algorithm,
+            range,
+            combined,
+            expected,
+        }) => {
+            let checksums =
+                checksum::checksum(&files, algorithm, range, combined, expected.as_deref())?;
+            let code = if checksums.iter().any(checksum::Checksum::is_mismatch) {
+                MISMATCH
+            } else {
+                OK
+            };
+            match args.format {
+                Format::Text => {
+                    for checksum in &checksums {
+                        println!("{checksum}");
+                    }
+                }
+                Format::Json | Format::Yaml => print_structured(args.format, &checksums)?,
+                Format::Csv => print_csv(checksums)?,
+            }
+            return Ok(code);
+        }
+        Some(Command::Scan { root }) => {
+            let rows = scan::scan(&root);
+            let code = if rows.iter().any(scan::ScanRow::is_error) {
+                NOINPUT
+            } else {
+                OK
+            };
+            match args.format {
+                Format::Text => {
+                    for row in &rows {
+                        println!("{row}");
+                    }
+                }
+                Format::Json | Format::Yaml => print_structured(args.format, &rows)?,
+                Format::Csv => print_csv(rows)?,
+            }
+            return Ok(code);
+        }
+        None => {}
+    }
+
+    let path = args.filename.ok_or(anyhow!("Missing --filename."))?;
+    show_file(&path, &args.show, &registry, args.format)?;
+    Ok(OK)
+}
+
+fn show_file(
+    path: &Path,
+    options: &ShowArgs,
+    registry: &ManufacturerRegistry,
+    format: Format,
+) -> anyhow::Result<()> {
+    let mut decoders = UserDataRegistry::default();
+    for prefix in &options.user_data_prefix {
+        let (prefix, name) = prefix
+            .split_once('=')
+            .ok_or(anyhow!("Expected PREFIX=NAME, found {prefix:?}."))?;
+        decoders.set_prefix(prefix, name)?;
+    }
+    let decoder = match &options.user_data_decoder {
+        Some(name) => Some(
+            decoders
+                .get(name)
+                .ok_or(UserDataError::UnknownDecoder(name.clone()))?,
+        ),
+        None => None,
+    };
+
     let filename = path
         .file_name()
         .ok_or(anyhow!("Valid filename from {path:?} not found."))?;
 
     match filename.to_str() {
         Some("LOADS.LUM") => {
-            let loads_lum = LoadsLum::new(&path)?;
-            println!("{loads_lum}");
+            let loads_lum = if options.strict {
+                LoadsLum::new_strict(path)?
+            } else {
+                LoadsLum::new(path)?
+            };
+            show(
+                &loads_lum,
+                registry,
+                &decoders,
+                decoder,
+                format,
+                options.table,
+            )?;
         }
         Some("FILES.LUM") => {
-            let files_lum = FilesLum::new(&path)?;
-            println!("{files_lum}");
+            let files_lum = if options.strict {
+                FilesLum::new_strict(path)?
+            } else {
+                FilesLum::new(path)?
+            };
+            show(
+                &files_lum,
+                registry,
+                &decoders,
+                decoder,
+                format,
+                options.table,
+            )?;
         }
         Some(file_name) => return Err(anyhow!("{file_name} not supported.")),
         None => return Err(anyhow!("Filename not supported.")),
     }
+    Ok(())
+}
 
+fn show<T>(
+    file: &T,
+    registry: &ManufacturerRegistry,
+    decoders: &UserDataRegistry,
+    decoder: Option<&dyn UserDataDecoder>,
+    format: Format,
+    table: Option<Table>,
+) -> anyhow::Result<()>
+where
+    T: Export + DisplayWithManufacturers,
+    for<'a> Annotated<'a, T>: Serialize,
+{
+    let decoded = match (decoder, file.get_user_defined_data()) {
+        (Some(decoder), Some(data)) => Some(decoder.decode(&data)),
+        _ => file.decode_user_defined_data(decoders),
+    }
+    .and_then(|decoded| match decoded {
+        Ok(decoded) => Some(decoded),
+        Err(e) => {
+            eprintln!("Warning: user defined data: {e}");
+            None
+        }
+    });
+
+    match format {
+        Format::Text => {
+            println!("{}", WithManufacturers::new(file, registry));
+            if let Some(decoded) = decoded {
+                println!(" User defined data:\n{decoded}");
+            }
+        }
+        Format::Json | Format::Yaml => {
+            print_structured(format, &Annotated::new(file, registry, decoded))?;
+        }
+        Format::Csv => file.print_csv(table, registry)?,
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct ConversionReport {
+    warnings: Vec<ConversionWarning>,
+}
+
+fn convert(
+    input: &Path,
+    format_version: FormatVersion,
+    output: &Path,
+    format: Format,
+) -> anyhow::Result<()> {
+    if !format_version.is_media_file() {
+        return Err(UsageError(format!(
+            "{format_version} is not a media file format version."
+        ))
+        .into());
+    }
+    if is_same_file(input, output)? {
+        return Err(UsageError(format!(
+            "{output:?} is the file being converted, write the result to another file."
+        ))
+        .into());
+    }
+    let warnings = match input.file_name().and_then(|x| x.to_str()) {
+        Some("LOADS.LUM") => convert_file::<LoadsLum>(input, format_version, output)?,
+        Some("FILES.LUM") => convert_file::<FilesLum>(input, format_version, output)?,
+        _ => return Err(anyhow!("{input:?} can't be converted.")),
+    };
+    match format {
+        Format::Text => {
+            for warning in warnings {
+                eprintln!("Warning: {warning}");
+            }
+        }
+        Format::Json | Format::Yaml => print_structured(format, &ConversionReport { warnings })?,
+        Format::Csv => print_csv(warnings)?,
+    }
+    Ok(())
+}
+
+// Returns `true` if `output` exists and is the same file as `input`.
+fn is_same_file(input: &Path, output: &Path) -> anyhow::Result<bool> {
+    Ok(output.exists() && fs::canonicalize(output)? == fs::canonicalize(input)?)
+}
+
+fn convert_file<T>(
+    input: &Path,
+    format_version: FormatVersion,
+    output: &Path,
+) -> anyhow::Result<Vec<ConversionWarning>>
+where
+    T: ArincFile,
+    T::Error: std::error::Error + Send + Sync + 'static,
+{
+    let mut file = T::from_path(input)?;
+    let warnings = file.convert(format_version)?;
+    fs::write(output, file.to_bytes()?)?;
+    Ok(warnings)
+}
+
+#[derive(Serialize)]
+struct ValidationReport {
+    findings: Vec<Finding>,
+    errors: usize,
+    warnings: usize,
+    infos: usize,
+}
+
+fn validate_media(media_root: &Path, format: Format) -> anyhow::Result<ExitCode> {
+    // A media whose LOADS.LUM or FILES.LUM can't be read is missing, one which
+    // doesn't parse is invalid.
+    let findings = match MediaSet::open(media_root) {
+        Ok(media_set) => validate(&media_set),
+        Err(
+            e @ (MediaSetError::FileIO(_)
+            | MediaSetError::LoadsLum(LoadsLumError::FileIO(_))
+            | MediaSetError::FilesLum(FilesLumError::FileIO(_))),
+        ) => {
+            eprintln!(
+                "Could not read {}: {}",
+                media_root.display(),
+                error_message(&e)
+            );
+            return Ok(NOINPUT);
+        }
+        Err(e) => vec![Finding {
+            severity: Severity::Error,
+            location: media_root.display().to_string(),
+            message: error_message(&e),
+        }],
+    };
+    let count = |severity| findings.iter().filter(|x| x.severity == severity).count();
+    let (errors, warnings, infos) = (
+        count(Severity::Error),
+        count(Severity::Warning),
+        count(Severity::Info),
+    );
+    let code = match findings.iter().map(|x| x.severity).max() {
+        Some(Severity::Error) => DATAERR,
+        Some(Severity::Warning) => WARNINGS,
+        Some(Severity::Info) | None => OK,
+    };
+    match format {
+        Format::Text => {
+            for finding in &findings {
+                println!("{finding}");
+            }
+            println!("{errors} errors, {warnings} warnings, {infos} infos");
+        }
+        Format::Json | Format::Yaml => print_structured(
+            format,
+            &ValidationReport {
+                findings,
+                errors,
+                warnings,
+                infos,
+            },
+        )?,
+        Format::Csv => print_csv(findings)?,
+    }
+    Ok(code)
+}
+
+fn print_tree(
+    media_root: &Path,
+    registry: &ManufacturerRegistry,
+    depth: Option<usize>,
+    color: ColorChoice,
+    format: Format,
+) -> anyhow::Result<()> {
+    let tree = tree::media_set_tree(&MediaSet::open(media_root)?, registry);
+    match format {
+        Format::Text => {
+            print!("{}", tree.render(depth, use_color(color)));
+        }
+        Format::Json | Format::Yaml => print_structured(format, &tree)?,
+        Format::Csv => return Err(UsageError("The tree can't be printed as CSV.".into()).into()),
+    }
+    Ok(())
+}
+
+fn print_explain<T>(input: &Path, color: ColorChoice, format: Format) -> anyhow::Result<()>
+where
+    T: ArincFile,
+    T::Error: std::error::Error + Send + Sync + 'static,
+{
+    let bytes = fs::read(input)?;
+    let file = T::from_bytes(&bytes)?;
+    let file_length = usize::try_from(file.get_header().get_file_length())? * 2;
+    let ranges = explain::explain(&bytes, &file.get_field_map()?, file_length);
+    match format {
+        Format::Text => print!("{}", explain::render(&ranges, use_color(color))),
+        Format::Json | Format::Yaml => print_structured(format, &ranges)?,
+        Format::Csv => print_csv(ranges)?,
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct DiffReport {
+    differences: Vec<Difference>,
+}
+
+fn diff_paths(old: &Path, new: &Path, format: Format) -> anyhow::Result<ExitCode> {
+    let differences = if old.is_dir() && new.is_dir() {
+        diff_media_sets(&MediaSet::open(old)?, &MediaSet::open(new)?)
+    } else {
+        let name = old.file_name().and_then(|x| x.to_str());
+        if name != new.file_name().and_then(|x| x.to_str()) {
+            return Err(anyhow!("{old:?} and {new:?} are not the same file type."));
+        }
+        match name {
+            Some("LOADS.LUM") => diff_loads_lum(&LoadsLum::new(old)?, &LoadsLum::new(new)?),
+            Some("FILES.LUM") => diff_files_lum(&FilesLum::new(old)?, &FilesLum::new(new)?),
+            _ => return Err(anyhow!("{old:?} can't be compared.")),
+        }
+    };
+    let code = if differences.is_empty() {
+        OK
+    } else {
+        DIFFERENT
+    };
+    match format {
+        Format::Text => {
+            for difference in &differences {
+                println!("{difference}");
+            }
+        }
+        Format::Json | Format::Yaml => print_structured(format, &DiffReport { differences })?,
+        Format::Csv => print_csv(differences)?,
+    }
+    Ok(code)
+}
+
+#[derive(Serialize)]
+struct RepairReport {
+    changes: Vec<RepairedValue>,
+}
+
+fn repair(input: &Path, output: &Path, in_place: bool, format: Format) -> anyhow::Result<()> {
+    if !in_place && is_same_file(input, output)? {
+        return Err(UsageError(format!(
+            "{output:?} is the file being repaired, pass --in-place to overwrite it."
+        ))
+        .into());
+    }
+    let bytes = fs::read(input)?;
+    let (repaired, changes) = match input.file_name().and_then(|x| x.to_str()) {
+        Some("LOADS.LUM") => {
+            let (file, changes) = repair_loads_lum(&bytes)?;
+            (file.to_bytes()?, changes)
+        }
+        Some("FILES.LUM") => {
+            let (file, changes) = repair_files_lum(&bytes)?;
+            (file.to_bytes()?, changes)
+        }
+        _ => return Err(anyhow!("{input:?} can't be repaired.")),
+    };
+    fs::write(output, repaired)?;
+    match format {
+        Format::Text => {
+            for change in &changes {
+                println!("{change}");
+            }
+        }
+        Format::Json | Format::Yaml => print_structured(format, &RepairReport { changes })?,
+        Format::Csv => print_csv(changes)?,
+    }
     Ok(())
 }
@@ -0,0 +1,218 @@
+//! The machine-readable output formats of the commands.
+
+use std::env;
+use std::io::{self, IsTerminal};
+
+use anyhow::anyhow;
+use arinc_explorer::arinc_file::MediaSetFile;
+use arinc_explorer::error::{FilesLumError, LoadsLumError, MediaSetError};
+use arinc_explorer::files::FilesLum;
+use arinc_explorer::loads::LoadsLum;
+use arinc_explorer::manufacturer::ManufacturerRegistry;
+use clap::{ColorChoice, ValueEnum};
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum Format {
+    /// The human readable output.
+    #[default]
+    Text,
+    /// The full structured model as JSON.
+    Json,
+    /// The full structured model as YAML.
+    Yaml,
+    /// Flat tables, one row per load, file, target HW ID or finding.
+    Csv,
+}
+
+/// Formats an error opening a media on one line. The parse errors are
+/// reported without the backtrace of the fields being parsed, only with the
+/// error which stopped the parsing.
+pub fn error_message(error: &MediaSetError) -> String {
+    match error {
+        MediaSetError::LoadsLum(LoadsLumError::BinRead(e)) => {
+            format!("LOADS.LUM: {}", e.root_cause())
+        }
+        MediaSetError::FilesLum(FilesLumError::BinRead(e)) => {
+            format!("FILES.LUM: {}", e.root_cause())
+        }
+        e => e.to_string(),
+    }
+}
+
+/// The table printed by `--format csv`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Table {
+    /// One row per load of `LOADS.LUM`, the default for `LOADS.LUM`.
+    Loads,
+    /// One row per target HW ID of each load of `LOADS.LUM`.
+    TargetHwIds,
+    /// One row per file of `FILES.LUM`, the default for `FILES.LUM`.
+    Files,
+}
+
+/// The colour of a piece of text output.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Style {
+    #[default]
+    Plain,
+    Bold,
+    Good,
+    Bad,
+    /// Entries of less interest, e.g. on another media of the set.
+    Dim,
+    Highlight,
+}
+impl Style {
+    /// Wraps `text` in the ANSI escape codes of the style.
+    #[must_use]
+    pub fn paint(self, text: &str, color: bool) -> String {
+        let code = match self {
+            Style::Plain => None,
+            Style::Bold => Some("1"),
+            Style::Good => Some("32"),
+            Style::Bad => Some("31"),
+            Style::Dim => Some("2"),
+            Style::Highlight => Some("33"),
+        };
+        match code.filter(|_| color) {
+            Some(code) => format!("\x1b[{code}m{text}\x1b[0m"),
+            None => text.to_string(),
+        }
+    }
+}
+
+/// Returns `true` if the text output should be coloured. `auto` colours it
+/// on a terminal, unless `NO_COLOR` is set.
+#[must_use]
+pub fn use_color(choice: ColorChoice) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none(),
+    }
+}
+
+/// Prints a value as JSON or YAML.
+pub fn print_structured<T: Serialize + ?Sized>(format: Format, value: &T) -> anyhow::Result<()> {
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(value)?),
+        // The YAML serializer already ends the document with a new line.
+        Format::Yaml => print!("{}", serde_yaml::to_string(value)?),
+        Format::Text | Format::Csv => {
+            return Err(anyhow!("{format:?} is not a structured format."));
+        }
+    }
+    Ok(())
+}
+
+/// Prints one CSV row per item, with a header row before the first one.
+pub fn print_csv<T: Serialize>(rows: impl IntoIterator<Item = T>) -> anyhow::Result<()> {
+    let mut writer = csv::Writer::from_writer(io::stdout());
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// A `LOADS.LUM` or `FILES.LUM` exported by the `json`, `yaml` and `csv`
+/// formats.
+pub trait Export: MediaSetFile + Serialize {
+    /// Prints the rows of `table`, or of the default table of the file.
+    fn print_csv(
+        &self,
+        table: Option<Table>,
+        registry: &ManufacturerRegistry,
+    ) -> anyhow::Result<()>;
+}
+
+#[derive(Serialize)]
+struct LoadRow {
+    media_set_pn: String,
+    media_set_supplier: Option<String>,
+    load_pn: String,
+    load_supplier: Option<String>,
+    header_filename: String,
+    member_sequence_number: u16,
+    /// The target HW IDs separated by `;`.
+    target_hw_ids: String,
+}
+
+#[derive(Serialize)]
+struct TargetHwRow {
+    media_set_pn: String,
+    load_pn: String,
+    target_hw_id: String,
+}
+
+#[derive(Serialize)]
+struct FileRow {
+    media_set_pn: String,
+    media_set_supplier: Option<String>,
+    file_pathname: String,
+    file_name: String,
+    member_sequence_number: u16,
+    file_crc: String,
+}
+
+impl Export for LoadsLum {
+    fn print_csv(
+        &self,
+        table: Option<Table>,
+        registry: &ManufacturerRegistry,
+    ) -> anyhow::Result<()> {
+        let media_set_pn = self.get_media_set_pn();
+        let loads = self.get_loads();
+        match table.unwrap_or(Table::Loads) {
+            Table::Loads => print_csv(loads.iter().map(|load| {
+                LoadRow {
+                    media_set_pn: media_set_pn.to_string(),
+                    media_set_supplier: registry.get_supplier(&media_set_pn).map(str::to_string),
+                    load_pn: load.get_load_pn().to_string(),
+                    load_supplier: registry
+                        .get_supplier(&load.get_load_pn())
+                        .map(str::to_string),
+                    header_filename: load.get_header_filename(),
+                    member_sequence_number: load.get_member_sequence_number(),
+                    target_hw_ids: load
+                        .get_target_hw_ids()
+                        .iter()
+                        .map(|x| x.get_target_hw_id())
+                        .collect::<Vec<_>>()
+                        .join(";"),
+                }
+            })),
+            Table::TargetHwIds => print_csv(loads.iter().flat_map(|load| {
+                let media_set_pn = media_set_pn.to_string();
+                load.get_target_hw_ids().iter().map(move |x| TargetHwRow {
+                    media_set_pn: media_set_pn.clone(),
+                    load_pn: load.get_load_pn().to_string(),
+                    target_hw_id: x.get_target_hw_id(),
+                })
+            })),
+            Table::Files => Err(anyhow!("LOADS.LUM doesn't have the files table.")),
+        }
+    }
+}
+
+impl Export for FilesLum {
+    fn print_csv(
+        &self,
+        table: Option<Table>,
+        registry: &ManufacturerRegistry,
+    ) -> anyhow::Result<()> {
+        if table.is_some_and(|x| x != Table::Files) {
+            return Err(anyhow!("FILES.LUM only has the files table."));
+        }
+        let media_set_pn = self.get_media_set_pn();
+        print_csv(self.get_media_set_files().iter().map(|file| FileRow {
+            media_set_pn: media_set_pn.to_string(),
+            media_set_supplier: registry.get_supplier(&media_set_pn).map(str::to_string),
+            file_pathname: file.get_file_pathname(),
+            file_name: file.get_file_name(),
+            member_sequence_number: file.get_member_sequence_number(),
+            file_crc: format!("0x{:04x}", file.get_file_crc()),
+        }))
+    }
+}
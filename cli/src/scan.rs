@@ -0,0 +1,308 @@
+//! The summary of every media set found under a directory, printed by the
+//! `scan` command.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::{fs, thread};
+
+use arinc_explorer::arinc_file::{ArincFile, MediaSetFile};
+use arinc_explorer::error::MediaSetError;
+use arinc_explorer::media_set::MediaSet;
+use arinc_explorer::validate::{validate, Severity};
+use serde::Serialize;
+
+use crate::output::error_message;
+
+/// A media set found by [`scan`], or a directory which couldn't be read.
+#[derive(Debug, Serialize)]
+pub struct ScanRow {
+    media_set_pn: Option<String>,
+    /// The root directories of the members found, separated by `;`.
+    path: String,
+    /// The members found out of the members of the set, e.g. `1,2 of 3`.
+    members: Option<String>,
+    loads: Option<usize>,
+    format_version: Option<String>,
+    /// `valid`, the number of errors and warnings found by `validate`, or
+    /// why the media couldn't be read.
+    status: String,
+}
+impl ScanRow {
+    #[must_use]
+    pub fn is_error(&self) -> bool {
+        self.media_set_pn.is_none()
+    }
+
+    // The error is printed on one line, in a row of its own.
+    fn error(path: &Path, error: impl Display) -> Self {
+        let error = error.to_string();
+        Self {
+            media_set_pn: None,
+            path: path.display().to_string(),
+            members: None,
+            loads: None,
+            format_version: None,
+            status: format!(
+                "error: {}",
+                error
+                    .lines()
+                    .map(str::trim)
+                    .filter(|x| !x.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+        }
+    }
+}
+impl Display for ScanRow {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match &self.media_set_pn {
+            Some(media_set_pn) => write!(
+                f,
+                "{media_set_pn}  {}  members {}  loads {}  {}  {}",
+                self.format_version.as_deref().unwrap_or_default(),
+                self.members.as_deref().unwrap_or_default(),
+                self.loads.unwrap_or_default(),
+                self.status,
+                self.path
+            ),
+            None => write!(f, "{}  {}", self.path, self.status),
+        }
+    }
+}
+
+// A media parsed and validated by a worker.
+struct Member {
+    media_set: MediaSet,
+    errors: usize,
+    warnings: usize,
+}
+
+// Collects the directories with a LOADS.LUM or a FILES.LUM. The directories
+// which can't be listed are reported as errors, the symbolic links are not
+// followed.
+fn find_media_roots(root: &Path, roots: &mut Vec<PathBuf>, rows: &mut Vec<ScanRow>) {
+    let entries = match fs::read_dir(root) {
+        Ok(x) => x,
+        Err(e) => return rows.push(ScanRow::error(root, e)),
+    };
+    let mut is_media_root = false;
+    let mut directories = Vec::new();
+    for entry in entries {
+        let entry = match entry {
+            Ok(x) => x,
+            Err(e) => return rows.push(ScanRow::error(root, e)),
+        };
+        match entry.file_type() {
+            Ok(x) if x.is_dir() => directories.push(entry.path()),
+            Ok(_) => {
+                is_media_root |=
+                    matches!(entry.file_name().to_str(), Some("LOADS.LUM" | "FILES.LUM"))
+            }
+            Err(e) => rows.push(ScanRow::error(&entry.path(), e)),
+        }
+    }
+    if is_media_root {
+        roots.push(root.to_path_buf());
+    }
+    directories.sort();
+    for directory in directories {
+        find_media_roots(&directory, roots, rows);
+    }
+}
+
+fn open_member(root: &Path) -> Result<Member, MediaSetError> {
+    let media_set = MediaSet::open(root)?;
+    let findings = validate(&media_set);
+    let count = |severity| findings.iter().filter(|x| x.severity == severity).count();
+    Ok(Member {
+        errors: count(Severity::Error),
+        warnings: count(Severity::Warning),
+        media_set,
+    })
+}
+
+// Runs `open` on `root` and returns the message of its error, or of its panic
+// so that the other media are still scanned.
+fn open_or_error<T>(
+    root: &Path,
+    open: impl FnOnce(&Path) -> Result<T, MediaSetError>,
+) -> Result<T, String> {
+    match panic::catch_unwind(AssertUnwindSafe(|| open(root))) {
+        Ok(result) => result.map_err(|e| error_message(&e)),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or(payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("unknown");
+            Err(format!("panic: {message}"))
+        }
+    }
+}
+
+// Summarises the members of a media set found in the same directory.
+fn summarise(members: &[Member]) -> ScanRow {
+    let first = &members[0].media_set;
+    let sequence_numbers = members
+        .iter()
+        .map(|x| {
+            x.media_set
+                .get_loads_lum()
+                .get_media_sequence_number()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    let (errors, warnings) = members
+        .iter()
+        .fold((0, 0), |(e, w), x| (e + x.errors, w + x.warnings));
+    let status = match (errors, warnings) {
+        (0, 0) => "valid".to_string(),
+        (0, w) => format!("{w} warnings"),
+        (e, w) => format!("{e} errors, {w} warnings"),
+    };
+    ScanRow {
+        media_set_pn: Some(first.get_loads_lum().get_media_set_pn().to_string()),
+        path: members
+            .iter()
+            .map(|x| x.media_set.get_root().display().to_string())
+            .collect::<Vec<_>>()
+            .join(";"),
+        members: Some(format!(
+            "{sequence_numbers} of {}",
+            first.get_loads_lum().get_number_of_media_set_members()
+        )),
+        loads: Some(first.get_loads_lum().get_loads().len()),
+        format_version: Some(first.get_loads_lum().get_format_version().to_string()),
+        status,
+    }
+}
+
+/// Finds the media under `root`, parses and validates them in parallel and
+/// returns one row per media set. The members of a media set are grouped
+/// when they are stored in the same directory, e.g. `SET/MEDIA1` and
+/// `SET/MEDIA2`. The media which can't be read are returned as error rows.
+#[must_use]
+pub fn scan(root: &Path) -> Vec<ScanRow> {
+    let mut roots = Vec::new();
+    let mut rows = Vec::new();
+    find_media_roots(root, &mut roots, &mut rows);
+
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::new());
+    let workers = thread::available_parallelism().map_or(1, usize::from);
+    thread::scope(|scope| {
+        for _ in 0..workers.min(roots.len()) {
+            scope.spawn(|| {
+                while let Some(root) = roots.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let result =
+                        open_or_error(root, open_member).map_err(|e| ScanRow::error(root, e));
+                    results
+                        .lock()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner)
+                        .push(result);
+                }
+            });
+        }
+    });
+
+    let mut sets: BTreeMap<(Option<PathBuf>, String), Vec<Member>> = BTreeMap::new();
+    for result in results
+        .into_inner()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+    {
+        match result {
+            Ok(member) => {
+                let key = (
+                    member.media_set.get_root().parent().map(Path::to_path_buf),
+                    member
+                        .media_set
+                        .get_loads_lum()
+                        .get_media_set_pn()
+                        .to_string(),
+                );
+                sets.entry(key).or_default().push(member);
+            }
+            Err(row) => rows.push(row),
+        }
+    }
+    for members in sets.values_mut() {
+        members.sort_by_key(|x| x.media_set.get_loads_lum().get_media_sequence_number());
+        rows.push(summarise(members));
+    }
+    rows.sort_by(|a, b| a.path.cmp(&b.path));
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Copies the test LOADS.LUM and FILES.LUM to `root`.
+    fn write_media(root: &Path) {
+        fs::create_dir_all(root).unwrap();
+        for name in ["LOADS.LUM", "FILES.LUM"] {
+            fs::copy(Path::new("../test-data").join(name), root.join(name)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_scan() {
+        let dir = tempfile::tempdir().unwrap();
+        write_media(&dir.path().join("SET/MEDIA1"));
+        fs::create_dir_all(dir.path().join("BAD")).unwrap();
+        fs::write(dir.path().join("BAD/LOADS.LUM"), b"garbage").unwrap();
+        fs::create_dir_all(dir.path().join("EMPTY")).unwrap();
+
+        let rows = scan(dir.path());
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].is_error());
+        assert!(rows[0].path.ends_with("BAD"));
+        assert!(rows[0].status.starts_with("error: "));
+        assert!(!rows[1].is_error());
+        assert_eq!(rows[1].media_set_pn.as_deref(), Some("ABCDEFGH12"));
+        assert_eq!(rows[1].members.as_deref(), Some("1 of 1"));
+        assert_eq!(rows[1].format_version.as_deref(), Some("ARINC665-1"));
+        assert!(rows[1]
+            .to_string()
+            .starts_with("ABCDEFGH12  ARINC665-1  members 1 of 1"));
+
+        assert!(scan(&dir.path().join("EMPTY")).is_empty());
+        assert!(scan(&dir.path().join("MISSING"))[0].is_error());
+    }
+
+    #[test]
+    fn test_scan_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        write_media(&dir.path().join("GOOD"));
+        let bad = dir.path().join("BAD");
+        write_media(&bad);
+        // pointer_to_user_defined_data is the file length, past the file CRC.
+        let mut loads_lum = fs::read(bad.join("LOADS.LUM")).unwrap();
+        loads_lum[14..18].copy_from_slice(&39u32.to_be_bytes());
+        fs::write(bad.join("LOADS.LUM"), loads_lum).unwrap();
+
+        let rows = scan(dir.path());
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[0].status,
+            "error: LOADS.LUM: pointer_to_user_defined_data 39 is past the end of the user \
+             defined data 38 at 0x4e"
+        );
+        assert!(!rows[1].is_error());
+
+        let error = open_or_error(&bad, |_| -> Result<(), MediaSetError> {
+            panic!("unexpected\ncontent")
+        })
+        .unwrap_err();
+        assert_eq!(
+            ScanRow::error(&bad, error).status,
+            "error: panic: unexpected content"
+        );
+    }
+}
@@ -0,0 +1,259 @@
+//! The hierarchy of a media set printed by the `tree` command.
+
+use std::fmt::Write;
+use std::fs;
+
+use arinc_explorer::arinc_file::{ArincFile, MediaSetFile};
+use arinc_explorer::files::File;
+use arinc_explorer::loads::Lsp;
+use arinc_explorer::manufacturer::ManufacturerRegistry;
+use arinc_explorer::media_set::MediaSet;
+use serde::Serialize;
+
+use crate::output::Style;
+
+/// An entry of the tree.
+#[derive(Debug, Default, Serialize)]
+pub struct Node {
+    label: String,
+    #[serde(skip)]
+    style: Style,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    children: Vec<Node>,
+}
+impl Node {
+    fn new(label: impl Into<String>, style: Style, children: Vec<Node>) -> Self {
+        Self {
+            label: label.into(),
+            style,
+            children,
+        }
+    }
+
+    fn leaf(label: impl Into<String>, style: Style) -> Self {
+        Self::new(label, style, Vec::new())
+    }
+
+    /// Renders the tree with box-drawing characters. The children of the
+    /// nodes deeper than `depth` are collapsed into a count.
+    #[must_use]
+    pub fn render(&self, depth: Option<usize>, color: bool) -> String {
+        let mut out = String::new();
+        self.write_label(&mut out, color);
+        self.write_children(&mut out, "", 0, depth, color);
+        out
+    }
+
+    fn write_label(&self, out: &mut String, color: bool) {
+        let _ = writeln!(out, "{}", self.style.paint(&self.label, color));
+    }
+
+    fn write_children(
+        &self,
+        out: &mut String,
+        prefix: &str,
+        level: usize,
+        depth: Option<usize>,
+        color: bool,
+    ) {
+        if self.children.is_empty() {
+            return;
+        }
+        if depth.is_some_and(|depth| level >= depth) {
+            let _ = writeln!(out, "{prefix}└── … {} more", self.children.len());
+            return;
+        }
+        for (i, child) in self.children.iter().enumerate() {
+            let last = i + 1 == self.children.len();
+            out.push_str(prefix);
+            out.push_str(if last { "└── " } else { "├── " });
+            child.write_label(out, color);
+            let prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+            child.write_children(out, &prefix, level + 1, depth, color);
+        }
+    }
+}
+
+fn part_number(part_number: &str, supplier: Option<&str>) -> String {
+    match supplier {
+        Some(supplier) => format!("{part_number} ({supplier})"),
+        None => part_number.to_string(),
+    }
+}
+
+fn load_node(load: &Lsp, registry: &ManufacturerRegistry) -> Node {
+    let label = format!(
+        "{} [{}]",
+        part_number(
+            load.get_load_pn().as_str(),
+            registry.get_supplier(&load.get_load_pn())
+        ),
+        load.get_header_filename()
+    );
+    let target_hw_ids = load
+        .get_target_hw_ids()
+        .iter()
+        .map(|x| Node::leaf(x.get_target_hw_id(), Style::Plain))
+        .collect();
+    Node::new(label, Style::Bold, target_hw_ids)
+}
+
+// The size and CRC status of a file of the media being shown.
+fn file_node(media_set: &MediaSet, file: &File) -> Node {
+    let name = file.get_file_name();
+    let path = match media_set.get_file_path(file) {
+        Ok(x) => x,
+        Err(e) => return Node::leaf(format!("{name}: {e}"), Style::Bad),
+    };
+    let Ok(metadata) = fs::metadata(&path) else {
+        return Node::leaf(format!("{name}: missing"), Style::Bad);
+    };
+    match media_set.has_valid_file_crc(file) {
+        Ok(true) => Node::leaf(
+            format!("{name}  {} bytes  CRC ok", metadata.len()),
+            Style::Good,
+        ),
+        Ok(false) => Node::leaf(
+            format!(
+                "{name}  {} bytes  CRC mismatch, expected 0x{:04x}",
+                metadata.len(),
+                file.get_file_crc()
+            ),
+            Style::Bad,
+        ),
+        Err(e) => Node::leaf(format!("{name}: {e}"), Style::Bad),
+    }
+}
+
+// The files of a member of the media set grouped by directory, in the order
+// of `FILES.LUM`.
+fn directory_nodes(media_set: &MediaSet, member: u16, present: bool) -> Vec<Node> {
+    let mut directories: Vec<(String, Vec<Node>)> = Vec::new();
+    let files = media_set.get_files_lum().get_media_set_files();
+    for file in files
+        .iter()
+        .filter(|x| x.get_member_sequence_number() == member)
+    {
+        let node = if present {
+            file_node(media_set, file)
+        } else {
+            Node::leaf(
+                format!(
+                    "{}  CRC 0x{:04x}",
+                    file.get_file_name(),
+                    file.get_file_crc()
+                ),
+                Style::Dim,
+            )
+        };
+        let pathname = file.get_file_pathname();
+        match directories.iter_mut().find(|(x, _)| *x == pathname) {
+            Some((_, nodes)) => nodes.push(node),
+            None => directories.push((pathname, vec![node])),
+        }
+    }
+    directories
+        .into_iter()
+        .map(|(pathname, nodes)| Node::new(pathname, Style::Plain, nodes))
+        .collect()
+}
+
+/// Builds the tree of a media set: its members, their loads with the target
+/// HW IDs and their files grouped by directory.
+///
+/// Only the files of the media being shown are checked, the others are
+/// listed as in `FILES.LUM`.
+#[must_use]
+pub fn media_set_tree(media_set: &MediaSet, registry: &ManufacturerRegistry) -> Node {
+    let loads_lum = media_set.get_loads_lum();
+    let current = loads_lum.get_media_sequence_number();
+    let members = (1..=loads_lum.get_number_of_media_set_members())
+        .map(|member| {
+            let present = member == current;
+            let loads = loads_lum
+                .get_loads()
+                .iter()
+                .filter(|x| x.get_member_sequence_number() == u16::from(member))
+                .map(|x| load_node(x, registry))
+                .collect();
+            let children = vec![
+                Node::new("Loads", Style::Plain, loads),
+                Node::new(
+                    "Files",
+                    Style::Plain,
+                    directory_nodes(media_set, u16::from(member), present),
+                ),
+            ];
+            if present {
+                Node::new(
+                    format!("Media {member} ({})", media_set.get_root().display()),
+                    Style::Bold,
+                    children,
+                )
+            } else {
+                Node::new(format!("Media {member}"), Style::Dim, children)
+            }
+        })
+        .collect();
+    let media_set_pn = loads_lum.get_media_set_pn();
+    Node::new(
+        format!(
+            "{} {}",
+            part_number(media_set_pn.as_str(), registry.get_supplier(&media_set_pn)),
+            loads_lum.get_format_version()
+        ),
+        Style::Bold,
+        members,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let tree = Node::new(
+            "root",
+            Style::Bold,
+            vec![
+                Node::new("a", Style::Plain, vec![Node::leaf("x", Style::Good)]),
+                Node::leaf("b", Style::Bad),
+            ],
+        );
+        assert_eq!(tree.render(None, false), "root\n├── a\n│   └── x\n└── b\n");
+        assert_eq!(
+            tree.render(Some(1), false),
+            "root\n├── a\n│   └── … 1 more\n└── b\n"
+        );
+        assert_eq!(tree.render(Some(0), false), "root\n└── … 2 more\n");
+        assert!(tree.render(None, true).contains('\x1b'));
+    }
+
+    #[test]
+    fn test_media_set_tree() {
+        let media_set = MediaSet::open(Path::new("../test-data")).unwrap();
+        let tree = media_set_tree(&media_set, &ManufacturerRegistry::default());
+        assert_eq!(tree.label, "ABCDEFGH12 ARINC665-1");
+        assert_eq!(tree.children.len(), 1);
+        let media = &tree.children[0];
+        assert!(media.label.starts_with("Media 1 ("));
+        assert_eq!(
+            media
+                .children
+                .iter()
+                .map(|x| x.label.as_str())
+                .collect::<Vec<_>>(),
+            ["Loads", "Files"]
+        );
+        let load = &media.children[0].children[0];
+        assert!(load.label.ends_with(".LUH]"), "{}", load.label);
+        assert!(!load.children.is_empty());
+        // The files listed in the test FILES.LUM are not stored with it.
+        assert!(tree
+            .render(None, false)
+            .ends_with("└── ABC1813001_CONF_XYZ01.XML: missing\n"));
+    }
+}
@@ -0,0 +1,127 @@
+use assert_cmd::Command;
+
+fn cli() -> Command {
+    let mut command = Command::cargo_bin("cli").unwrap();
+    command.current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/.."));
+    command
+}
+
+#[test]
+fn test_global_options_before_subcommand() {
+    let output = cli()
+        .args(["--format", "json", "validate", "test-data"])
+        .output()
+        .unwrap();
+    // The CRCs of the test files don't match their content.
+    assert_eq!(output.status.code(), Some(65));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(report["findings"].is_array());
+
+    cli()
+        .args(["validate", "test-data", "--format", "json"])
+        .assert()
+        .code(65);
+}
+
+#[test]
+fn test_show() {
+    let output = cli()
+        .args(["show", "test-data/LOADS.LUM", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let loads_lum: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(loads_lum["media_set_pn"], "ABCDEFGH12");
+
+    let legacy = cli()
+        .args(["--format", "json", "--filename", "test-data/LOADS.LUM"])
+        .output()
+        .unwrap();
+    assert!(legacy.status.success());
+    assert_eq!(legacy.stdout, output.stdout);
+
+    cli()
+        .args(["show", "test-data/FILES.LUM", "--format", "csv"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_usage_errors() {
+    cli().assert().code(2);
+    cli()
+        .args(["--filename", "test-data/LOADS.LUM", "validate", "test-data"])
+        .assert()
+        .code(2);
+    cli()
+        .args(["--strict", "validate", "test-data"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn test_invalid_arguments() {
+    cli().args(["explain", "README.md"]).assert().code(64);
+    cli()
+        .args(["checksum", "test-data/LOADS.LUM", "--range", "10..5"])
+        .assert()
+        .code(64);
+    cli()
+        .args(["checksum", "test-data/LOADS.LUM", "--expected", "xyz"])
+        .assert()
+        .code(64);
+    cli()
+        .args(["checksum", "test-data/LOADS.LUM", "--range", "10"])
+        .assert()
+        .code(2);
+    let dir = tempfile::tempdir().unwrap();
+    cli()
+        .args(["convert", "test-data/LOADS.LUM", "--to", "ARINC665-3 load"])
+        .arg("--output")
+        .arg(dir.path().join("LOADS.LUM"))
+        .assert()
+        .code(64);
+    assert!(!dir.path().join("LOADS.LUM").exists());
+    cli()
+        .args([
+            "repair",
+            "test-data/LOADS.LUM",
+            "--output",
+            "test-data/LOADS.LUM",
+        ])
+        .assert()
+        .code(64);
+}
+
+#[test]
+fn test_validate_unreadable_media() {
+    let dir = tempfile::tempdir().unwrap();
+    cli().arg("validate").arg(dir.path()).assert().code(66);
+
+    let loads_lum = std::fs::read("../test-data/LOADS.LUM").unwrap();
+    std::fs::write(dir.path().join("LOADS.LUM"), &loads_lum[..30]).unwrap();
+    std::fs::copy("../test-data/FILES.LUM", dir.path().join("FILES.LUM")).unwrap();
+    let output = cli().arg("validate").arg(dir.path()).output().unwrap();
+    assert_eq!(output.status.code(), Some(65));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("error: "), "{stdout}");
+    assert!(stdout.contains(": LOADS.LUM: "), "{stdout}");
+    assert_eq!(stdout.lines().count(), 2, "{stdout}");
+    assert!(!stdout.contains('\x1b'));
+}
+
+#[test]
+fn test_convert_in_place() {
+    let dir = tempfile::tempdir().unwrap();
+    let files_lum = dir.path().join("FILES.LUM");
+    std::fs::copy("../test-data/FILES.LUM", &files_lum).unwrap();
+    let original = std::fs::read(&files_lum).unwrap();
+    cli()
+        .arg("convert")
+        .arg(&files_lum)
+        .args(["--to", "ARINC665-2", "--output"])
+        .arg(dir.path().join(".").join("FILES.LUM"))
+        .assert()
+        .code(64);
+    assert_eq!(std::fs::read(&files_lum).unwrap(), original);
+}
@@ -0,0 +1,408 @@
+//! Python bindings of `arinc-explorer`.
+//!
+//! The extension module is built with `maturin`, see `pyproject.toml`.
+
+#![warn(
+    clippy::all,
+    clippy::cargo,
+    clippy::pedantic,
+    // clippy::missing_docs_in_private_items,
+    clippy::unwrap_used
+)]
+// pyo3 extracts the arguments by value, e.g. paths as `PathBuf`.
+#![allow(clippy::needless_pass_by_value)]
+
+use std::path::PathBuf;
+
+use arinc_explorer::arinc_file::{ArincFile, MediaSetFile};
+use arinc_explorer::error::{FilesLumError, LoadHeaderError, LoadsLumError, MediaSetError};
+use arinc_explorer::files::{File, FilesLum};
+use arinc_explorer::load_header::LoadHeaderFiles;
+use arinc_explorer::loads::{LoadsLum, Lsp};
+use arinc_explorer::media_set::MediaSet;
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pyo3::types::{PyIterator, PyList};
+
+create_exception!(
+    arinc_explorer,
+    ArincError,
+    PyException,
+    "Base class of the errors raised by the module."
+);
+create_exception!(
+    arinc_explorer,
+    ParseError,
+    ArincError,
+    "The content of a file is not valid."
+);
+create_exception!(
+    arinc_explorer,
+    UnsafePathError,
+    ArincError,
+    "A pathname in FILES.LUM points outside the media."
+);
+
+// I/O errors are raised as the matching `OSError` subclass, e.g.
+// `FileNotFoundError`.
+fn loads_lum_error(error: LoadsLumError) -> PyErr {
+    match error {
+        LoadsLumError::FileIO(e) => e.into(),
+        e => ParseError::new_err(e.to_string()),
+    }
+}
+
+fn files_lum_error(error: FilesLumError) -> PyErr {
+    match error {
+        FilesLumError::FileIO(e) => e.into(),
+        e => ParseError::new_err(e.to_string()),
+    }
+}
+
+fn load_header_error(error: LoadHeaderError) -> PyErr {
+    ParseError::new_err(error.to_string())
+}
+
+fn media_set_error(error: MediaSetError) -> PyErr {
+    match error {
+        MediaSetError::FileIO(e) => e.into(),
+        MediaSetError::LoadsLum(e) => loads_lum_error(e),
+        MediaSetError::FilesLum(e) => files_lum_error(e),
+        e @ MediaSetError::UnsafePath(_) => UnsafePathError::new_err(e.to_string()),
+    }
+}
+
+fn iterate<'py, T>(py: Python<'py>, entries: Vec<T>) -> PyResult<Bound<'py, PyIterator>>
+where
+    T: IntoPyObject<'py>,
+{
+    PyList::new(py, entries)?.try_iter()
+}
+
+/// A load listed in `LOADS.LUM`.
+#[pyclass(name = "Load", module = "arinc_explorer", frozen)]
+pub struct PyLoad(Lsp);
+#[pymethods]
+impl PyLoad {
+    #[getter]
+    fn load_pn(&self) -> String {
+        self.0.get_load_pn().as_str().to_string()
+    }
+
+    /// The name of the load header file.
+    #[getter]
+    fn header_filename(&self) -> String {
+        self.0.get_header_filename()
+    }
+
+    #[getter]
+    fn member_sequence_number(&self) -> u16 {
+        self.0.get_member_sequence_number()
+    }
+
+    #[getter]
+    fn target_hw_ids(&self) -> Vec<String> {
+        self.0
+            .get_target_hw_ids()
+            .iter()
+            .map(arinc_explorer::loads::TargetHW::get_target_hw_id)
+            .collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Load(load_pn={:?})", self.load_pn())
+    }
+}
+
+/// A `LOADS.LUM` file. Iterating yields its loads.
+#[pyclass(name = "LoadsLum", module = "arinc_explorer", frozen)]
+pub struct PyLoadsLum(LoadsLum);
+#[pymethods]
+impl PyLoadsLum {
+    /// Parses the file at `path`. With `strict`, strings which don't conform
+    /// to the ARINC 665 character sets raise a `ParseError`.
+    #[new]
+    #[pyo3(signature = (path, strict = false))]
+    fn new(path: PathBuf, strict: bool) -> PyResult<Self> {
+        let loads_lum = if strict {
+            LoadsLum::new_strict(&path)
+        } else {
+            LoadsLum::new(&path)
+        };
+        Ok(Self(loads_lum.map_err(loads_lum_error)?))
+    }
+
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        Ok(Self(LoadsLum::from_bytes(data).map_err(loads_lum_error)?))
+    }
+
+    #[getter]
+    fn media_set_pn(&self) -> String {
+        self.0.get_media_set_pn().as_str().to_string()
+    }
+
+    #[getter]
+    fn media_sequence_number(&self) -> u8 {
+        self.0.get_media_sequence_number()
+    }
+
+    #[getter]
+    fn number_of_media_set_members(&self) -> u8 {
+        self.0.get_number_of_media_set_members()
+    }
+
+    #[getter]
+    fn format_version(&self) -> String {
+        self.0.get_format_version().to_string()
+    }
+
+    #[getter]
+    fn file_crc(&self) -> u16 {
+        self.0.get_file_crc()
+    }
+
+    #[getter]
+    fn loads(&self) -> Vec<PyLoad> {
+        self.0.get_loads().iter().cloned().map(PyLoad).collect()
+    }
+
+    /// Returns `True` if the CRC stored in the file matches its content.
+    fn has_valid_file_crc(&self) -> PyResult<bool> {
+        self.0.has_valid_file_crc().map_err(loads_lum_error)
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.get_loads().len()
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        iterate(py, self.loads())
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// A file listed in `FILES.LUM`.
+#[pyclass(name = "File", module = "arinc_explorer", frozen)]
+pub struct PyFile(File);
+#[pymethods]
+impl PyFile {
+    #[getter]
+    fn name(&self) -> String {
+        self.0.get_file_name()
+    }
+
+    /// The directory of the file, e.g. `\DIR\`.
+    #[getter]
+    fn pathname(&self) -> String {
+        self.0.get_file_pathname()
+    }
+
+    #[getter]
+    fn member_sequence_number(&self) -> u16 {
+        self.0.get_member_sequence_number()
+    }
+
+    #[getter]
+    fn crc(&self) -> u16 {
+        self.0.get_file_crc()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "File(pathname={:?}, name={:?})",
+            self.pathname(),
+            self.name()
+        )
+    }
+}
+
+/// A `FILES.LUM` file. Iterating yields its files.
+#[pyclass(name = "FilesLum", module = "arinc_explorer", frozen)]
+pub struct PyFilesLum(FilesLum);
+#[pymethods]
+impl PyFilesLum {
+    /// Parses the file at `path`. With `strict`, strings which don't conform
+    /// to the ARINC 665 character sets raise a `ParseError`.
+    #[new]
+    #[pyo3(signature = (path, strict = false))]
+    fn new(path: PathBuf, strict: bool) -> PyResult<Self> {
+        let files_lum = if strict {
+            FilesLum::new_strict(&path)
+        } else {
+            FilesLum::new(&path)
+        };
+        Ok(Self(files_lum.map_err(files_lum_error)?))
+    }
+
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        Ok(Self(FilesLum::from_bytes(data).map_err(files_lum_error)?))
+    }
+
+    #[getter]
+    fn media_set_pn(&self) -> String {
+        self.0.get_media_set_pn().as_str().to_string()
+    }
+
+    #[getter]
+    fn media_sequence_number(&self) -> u8 {
+        self.0.get_media_sequence_number()
+    }
+
+    #[getter]
+    fn number_of_media_set_members(&self) -> u8 {
+        self.0.get_number_of_media_set_members()
+    }
+
+    #[getter]
+    fn format_version(&self) -> String {
+        self.0.get_format_version().to_string()
+    }
+
+    #[getter]
+    fn file_crc(&self) -> u16 {
+        self.0.get_file_crc()
+    }
+
+    #[getter]
+    fn files(&self) -> Vec<PyFile> {
+        self.0
+            .get_media_set_files()
+            .iter()
+            .cloned()
+            .map(PyFile)
+            .collect()
+    }
+
+    /// Returns `True` if the CRC stored in the file matches its content.
+    fn has_valid_file_crc(&self) -> PyResult<bool> {
+        self.0.has_valid_file_crc().map_err(files_lum_error)
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.get_media_set_files().len()
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        iterate(py, self.files())
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// The data and support files listed in a load header file (`.LUH`). The
+/// rest of the load header isn't parsed.
+#[pyclass(name = "LoadHeader", module = "arinc_explorer", frozen)]
+pub struct PyLoadHeader(LoadHeaderFiles);
+#[pymethods]
+impl PyLoadHeader {
+    #[new]
+    fn new(path: PathBuf) -> PyResult<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        Ok(Self(
+            LoadHeaderFiles::from_bytes(data).map_err(load_header_error)?,
+        ))
+    }
+
+    /// The names of the data files.
+    #[getter]
+    fn data_files(&self) -> Vec<String> {
+        self.0.get_data_files().to_vec()
+    }
+
+    /// The names of the support files.
+    #[getter]
+    fn support_files(&self) -> Vec<String> {
+        self.0.get_support_files().to_vec()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "LoadHeader(data_files={:?}, support_files={:?})",
+            self.data_files(),
+            self.support_files()
+        )
+    }
+}
+
+/// The media of a media set, a directory with `LOADS.LUM` and `FILES.LUM` at
+/// its root.
+#[pyclass(name = "MediaSet", module = "arinc_explorer", frozen)]
+pub struct PyMediaSet(MediaSet);
+#[pymethods]
+impl PyMediaSet {
+    #[new]
+    fn new(root: PathBuf) -> PyResult<Self> {
+        Ok(Self(MediaSet::open(&root).map_err(media_set_error)?))
+    }
+
+    #[getter]
+    fn root(&self) -> PathBuf {
+        self.0.get_root().to_path_buf()
+    }
+
+    #[getter]
+    fn loads_lum(&self) -> PyLoadsLum {
+        PyLoadsLum(self.0.get_loads_lum().clone())
+    }
+
+    #[getter]
+    fn files_lum(&self) -> PyFilesLum {
+        PyFilesLum(self.0.get_files_lum().clone())
+    }
+
+    /// Returns the location of a file listed in `FILES.LUM`.
+    fn file_path(&self, file: &PyFile) -> PyResult<PathBuf> {
+        self.0.get_file_path(&file.0).map_err(media_set_error)
+    }
+
+    /// Reads `file` and returns `True` if its CRC matches `FILES.LUM`.
+    fn has_valid_file_crc(&self, file: &PyFile) -> PyResult<bool> {
+        self.0.has_valid_file_crc(&file.0).map_err(media_set_error)
+    }
+
+    /// Checks the CRC of every file listed in `FILES.LUM`. Returns the
+    /// `(file, valid)` pairs, `valid` is `None` if the file can't be read.
+    /// Raises `UnsafePathError` if a pathname points outside the media.
+    fn verify(&self) -> PyResult<Vec<(PyFile, Option<bool>)>> {
+        self.0
+            .get_files_lum()
+            .get_media_set_files()
+            .iter()
+            .map(|x| {
+                let valid = match self.0.has_valid_file_crc(x) {
+                    Ok(valid) => Some(valid),
+                    Err(MediaSetError::FileIO(_)) => None,
+                    Err(e) => return Err(media_set_error(e)),
+                };
+                Ok((PyFile(x.clone()), valid))
+            })
+            .collect()
+    }
+}
+
+#[pymodule]
+#[pyo3(name = "arinc_explorer")]
+fn python_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("ArincError", m.py().get_type::<ArincError>())?;
+    m.add("ParseError", m.py().get_type::<ParseError>())?;
+    m.add("UnsafePathError", m.py().get_type::<UnsafePathError>())?;
+    m.add_class::<PyLoad>()?;
+    m.add_class::<PyLoadsLum>()?;
+    m.add_class::<PyFile>()?;
+    m.add_class::<PyFilesLum>()?;
+    m.add_class::<PyLoadHeader>()?;
+    m.add_class::<PyMediaSet>()?;
+    Ok(())
+}
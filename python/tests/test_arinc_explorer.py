@@ -0,0 +1,105 @@
+import shutil
+from pathlib import Path
+
+import pytest
+
+import arinc_explorer
+from arinc_explorer import FilesLum, LoadHeader, LoadsLum, MediaSet
+
+TEST_DATA = Path(__file__).resolve().parents[2] / "test-data"
+
+
+def test_loads_lum():
+    loads_lum = LoadsLum(TEST_DATA / "LOADS.LUM")
+    assert loads_lum.media_set_pn == "ABCDEFGH12"
+    assert loads_lum.media_sequence_number == 1
+    assert loads_lum.number_of_media_set_members == 1
+    assert len(loads_lum) == 1
+
+    [load] = loads_lum
+    assert load.load_pn == "ABCDEFGH12"
+    assert load.header_filename == "ABCDEFGH12.LUH"
+    assert load.member_sequence_number == 1
+    assert load.target_hw_ids == ["TYUIOP"]
+    # The stored CRC is 0x5246, the computed one 0xee35.
+    assert loads_lum.has_valid_file_crc() is False
+
+
+def test_files_lum():
+    files_lum = FilesLum.from_bytes((TEST_DATA / "FILES.LUM").read_bytes())
+    assert files_lum.media_set_pn == "ABC1813001"
+    assert len(files_lum) == 14
+
+    file = next(iter(files_lum))
+    assert file.name == "ABC1813001.LUH"
+    assert file.pathname == "\\ABC1813001\\"
+    assert file.crc == 0xD23E
+    assert [x.name for x in files_lum] == [x.name for x in files_lum.files]
+
+
+def test_errors():
+    with pytest.raises(FileNotFoundError):
+        LoadsLum(TEST_DATA / "missing" / "LOADS.LUM")
+    with pytest.raises(arinc_explorer.ParseError):
+        LoadsLum.from_bytes(b"\x00\x00")
+    assert issubclass(arinc_explorer.ParseError, arinc_explorer.ArincError)
+
+
+def test_media_set(tmp_path):
+    shutil.copy(TEST_DATA / "LOADS.LUM", tmp_path)
+    shutil.copy(TEST_DATA / "FILES.LUM", tmp_path)
+    (tmp_path / "ABC1813001").mkdir()
+    (tmp_path / "ABC1813001" / "ABC1813001.LUH").write_bytes(b"header")
+
+    media_set = MediaSet(tmp_path)
+    assert len(media_set.loads_lum) == 1
+    files = media_set.files_lum.files
+    assert media_set.file_path(files[0]) == tmp_path / "ABC1813001" / "ABC1813001.LUH"
+    assert not media_set.has_valid_file_crc(files[0])
+    with pytest.raises(FileNotFoundError):
+        media_set.has_valid_file_crc(files[1])
+
+    results = media_set.verify()
+    assert len(results) == 14
+    assert results[0][1] is False
+    assert all(valid is None for _, valid in results[1:])
+
+
+def test_media_set_unsafe_path(tmp_path):
+    files_lum = bytearray((TEST_DATA / "FILES.LUM").read_bytes())
+    # The pathname of the first file, \ABC1813001\, becomes \..\1813001\.
+    start = files_lum.index(b"\\ABC1813001\\")
+    files_lum[start + 1 : start + 4] = b"..\\"
+    shutil.copy(TEST_DATA / "LOADS.LUM", tmp_path)
+    (tmp_path / "FILES.LUM").write_bytes(files_lum)
+
+    media_set = MediaSet(tmp_path)
+    with pytest.raises(arinc_explorer.UnsafePathError):
+        media_set.verify()
+
+
+def test_load_header(tmp_path):
+    words = [
+        # file_length, format_version, part_flags
+        0x0000, 0x001C, 0x8003, 0x0000,
+        # pointers to load PN, target HW IDs, data files, support files
+        0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x000E, 0x0000, 0x0016,
+        # pointer to user defined data, unused
+        0x0000, 0x0000,
+        # 2 data files, the first entry points 4 words after
+        0x0002, 0x0004, 0x0003, 0x4142, 0x4300, 0x0000, 0x0002, 0x4445,
+        # 1 support file
+        0x0001, 0x0000, 0x0001, 0x4600,
+    ]
+    data = b"".join(x.to_bytes(2, "big") for x in words)
+    (tmp_path / "LOAD.LUH").write_bytes(data)
+
+    load_header = LoadHeader(tmp_path / "LOAD.LUH")
+    assert load_header.data_files == ["ABC", "DE"]
+    assert load_header.support_files == ["F"]
+    assert LoadHeader.from_bytes(data).data_files == ["ABC", "DE"]
+
+    with pytest.raises(arinc_explorer.ParseError):
+        LoadHeader.from_bytes(data[:40])
+    with pytest.raises(FileNotFoundError):
+        LoadHeader(tmp_path / "MISSING.LUH")
@@ -0,0 +1,44 @@
+//! WebAssembly bindings of `arinc-explorer`.
+//!
+//! The files are parsed from their content and returned as plain JavaScript
+//! objects with the structured representation of the `serde` feature.
+
+#![warn(
+    clippy::all,
+    clippy::cargo,
+    clippy::pedantic,
+    // clippy::missing_docs_in_private_items,
+    clippy::unwrap_used
+)]
+
+use arinc_explorer::arinc_file::ArincFile;
+use arinc_explorer::files::FilesLum;
+use arinc_explorer::loads::LoadsLum;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+// Maps are converted to objects rather than `Map`s, so the result can be
+// passed to `JSON.stringify`.
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    Ok(value.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?)
+}
+
+/// Parses the content of a `LOADS.LUM` file.
+///
+/// # Errors
+///
+/// Throws an `Error` if the content is not a valid `LOADS.LUM`.
+#[wasm_bindgen(js_name = parseLoadsLum)]
+pub fn parse_loads_lum(bytes: &[u8]) -> Result<JsValue, JsError> {
+    to_js(&LoadsLum::from_bytes(bytes)?)
+}
+
+/// Parses the content of a `FILES.LUM` file.
+///
+/// # Errors
+///
+/// Throws an `Error` if the content is not a valid `FILES.LUM`.
+#[wasm_bindgen(js_name = parseFilesLum)]
+pub fn parse_files_lum(bytes: &[u8]) -> Result<JsValue, JsError> {
+    to_js(&FilesLum::from_bytes(bytes)?)
+}
@@ -0,0 +1,36 @@
+//! Run with `wasm-pack test --node wasm`.
+
+#![cfg(target_arch = "wasm32")]
+
+use arinc_explorer_wasm::{parse_files_lum, parse_loads_lum};
+use serde_json::Value;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn to_json(value: JsValue) -> Value {
+    serde_wasm_bindgen::from_value(value).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn test_parse_loads_lum() {
+    let loads_lum = to_json(parse_loads_lum(include_bytes!("../../test-data/LOADS.LUM")).unwrap());
+    assert_eq!(loads_lum["media_set_pn"], "ABCDEFGH12");
+    assert_eq!(loads_lum["format_version"], "ARINC665-1");
+    assert_eq!(loads_lum["loads"][0]["target_hw_ids"][0], "TYUIOP");
+}
+
+#[wasm_bindgen_test]
+fn test_parse_files_lum() {
+    let files_lum = to_json(parse_files_lum(include_bytes!("../../test-data/FILES.LUM")).unwrap());
+    assert_eq!(files_lum["media_set_pn"], "ABC1813001");
+    assert_eq!(files_lum["media_set_files"].as_array().unwrap().len(), 14);
+    assert_eq!(
+        files_lum["media_set_files"][0]["file_name"],
+        "ABC1813001.LUH"
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_parse_error() {
+    assert!(parse_loads_lum(&[0, 0]).is_err());
+}